@@ -1,7 +1,30 @@
 use anchor_lang::prelude::*;
-use crate::state::Vault;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use anchor_spl::token_interface::{self, TokenInterface};
+use crate::state::{
+    Vault, LazyVault, VaultDelegate, VaultMultisig, MultisigProposal, OwnerIndex,
+    DepositorReceipt, Stream, VaultHistory, VaultStake, GuardianSet, WithdrawalAuthorization,
+    VaultSession, PoolShares, AllowedDestination, Governance, GovernanceMember, SpendProposal,
+    VoteRecord, Subscription, VaultTokenTotals, MAX_VAULT_NAME_LEN, STAKE_CONFIG_ID, STAKE_HISTORY_SYSVAR_ID, INSTRUCTIONS_SYSVAR_ID,
+};
+use crate::errors::VaultError;
 
 // All account structs in one place for easier access by #[program] macro
+//
+// The vault PDA is derived from `vault.creator` (the key that initialized it), not from
+// `vault.owner`, so ownership can move without changing the vault address.
+// Owner-only contexts therefore check `vault.is_owner(signer, ownership_token)` explicitly.
+//
+// Contexts that change vault state carry #[event_cpi] so their handlers can emit_cpi!
+// events, which are recorded as instruction data and survive log truncation.
+//
+// `vault.name` is the last seed. The default vault has an empty name, and an empty seed
+// adds nothing to the PDA hash, so it keeps the original `[b"vault", creator]` address.
+//
+// A tokenized vault's owner is its ownership mint, which nobody can sign for. Every owner
+// check takes an optional `ownership_token` and also accepts the holder of that token.
+#[event_cpi]
 #[derive(Accounts)]
 pub struct InitializeVault<'info> {
     #[account(mut)]
@@ -17,8 +40,32 @@ pub struct InitializeVault<'info> {
     pub vault: Account<'info, Vault>,
 
     pub system_program: Program<'info, System>,
+
+    // Pass the accounts below to make the vault tokenized: a 0-decimal mint with the vault
+    // as mint authority, and the signer's token account that receives its only token
+    #[account(
+        init,
+        payer = signer,
+        mint::decimals = 0,
+        mint::authority = vault,
+        seeds = [b"ownership_mint", vault.key().as_ref()],
+        bump,
+    )]
+    pub ownership_mint: Option<Account<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = signer,
+        associated_token::mint = ownership_mint,
+        associated_token::authority = signer,
+    )]
+    pub ownership_token: Option<Account<'info, TokenAccount>>,
+
+    pub token_program: Option<Program<'info, Token>>,
+    pub associated_token_program: Option<Program<'info, AssociatedToken>>,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct VaultAction<'info> {
     #[account(mut)]
@@ -26,41 +73,1843 @@ pub struct VaultAction<'info> {
 
     #[account(
         mut,
-        seeds = [b"vault", signer.key().as_ref()],
+        seeds = [b"vault", vault.creator.as_ref(), vault.name.as_bytes()],
         bump,
+        constraint = vault.is_owner(&signer.key(), ownership_token.as_ref()) @ VaultError::Unauthorized,
+        constraint = !vault.history_attached || history.is_some() @ VaultError::HistoryRequired,
     )]
     pub vault: Account<'info, Vault>,
 
     pub system_program: Program<'info, System>,
+
+    // The vault's history account, to record this operation in it - required once attached
+    #[account(
+        mut,
+        seeds = [b"history", vault.key().as_ref()],
+        bump,
+    )]
+    pub history: Option<AccountLoader<'info, VaultHistory>>,
+
+    // The signer's ownership token, for tokenized vaults
+    pub ownership_token: Option<Account<'info, TokenAccount>>,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct WithdrawAction<'info> {
     #[account(mut)]
     pub signer: Signer<'info>,
 
+    // Multisig vaults withdraw through execute_proposal instead
     #[account(
         mut,
-        seeds = [b"vault", signer.key().as_ref()],
+        seeds = [b"vault", vault.creator.as_ref(), vault.name.as_bytes()],
         bump,
+        constraint = vault.is_owner(&signer.key(), ownership_token.as_ref()) @ VaultError::Unauthorized,
+        constraint = !vault.multisig @ VaultError::MultisigEnabled,
+        constraint = !vault.history_attached || history.is_some() @ VaultError::HistoryRequired,
     )]
     pub vault: Account<'info, Vault>,
 
     pub system_program: Program<'info, System>,
+
+    // The vault's history account, to record this operation in it - required once attached
+    #[account(
+        mut,
+        seeds = [b"history", vault.key().as_ref()],
+        bump,
+    )]
+    pub history: Option<AccountLoader<'info, VaultHistory>>,
+
+    // The signer's ownership token, for tokenized vaults
+    pub ownership_token: Option<Account<'info, TokenAccount>>,
+
+    // The recipient's allowlist entry - required while the vault enforces its allowlist
+    pub allowed_destination: Option<Account<'info, AllowedDestination>>,
 }
 
 #[derive(Accounts)]
 pub struct ReadVaultAction<'info> {
-    #[account(mut)]
-    pub signer: Signer<'info>,
+    // No signer: anyone can read, and with only read-only accounts clients can call it as a view
 
     // Using LazyAccount for read-only access to vault data
     // LazyAccount uses only 24 bytes of stack memory and lets you selectively load fields
     #[account(
-        seeds = [b"vault", signer.key().as_ref()],
+        seeds = [b"vault", vault.load_creator()?.as_ref(), vault.load_name()?.as_bytes()],
         bump,
     )]
     pub vault: LazyAccount<'info, Vault>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct LockAction<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", vault.creator.as_ref(), vault.name.as_bytes()],
+        bump,
+        constraint = vault.is_owner(&signer.key(), ownership_token.as_ref()) @ VaultError::Unauthorized,
+        constraint = !vault.multisig @ VaultError::MultisigEnabled,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    // The signer's ownership token, for tokenized vaults
+    pub ownership_token: Option<Account<'info, TokenAccount>>,
+}
+
+#[derive(Accounts)]
+#[instruction(delegate: Pubkey)]
+pub struct AddDelegate<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", vault.creator.as_ref(), vault.name.as_bytes()],
+        bump,
+        constraint = vault.is_owner(&signer.key(), ownership_token.as_ref()) @ VaultError::Unauthorized,
+        // Key-bound grants would outlive a sale of the ownership token
+        constraint = !vault.tokenized @ VaultError::VaultTokenized,
+        constraint = !vault.multisig @ VaultError::MultisigEnabled,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        init,
+        payer = signer,
+        space = VaultDelegate::INIT_SPACE + VaultDelegate::DISCRIMINATOR.len(),
+        seeds = [b"delegate", vault.key().as_ref(), delegate.as_ref()],
+        bump,
+    )]
+    pub vault_delegate: Account<'info, VaultDelegate>,
+
+    pub system_program: Program<'info, System>,
+
+    // The signer's ownership token, for tokenized vaults
+    pub ownership_token: Option<Account<'info, TokenAccount>>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeDelegate<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", vault.creator.as_ref(), vault.name.as_bytes()],
+        bump,
+        constraint = vault.is_owner(&signer.key(), ownership_token.as_ref()) @ VaultError::Unauthorized,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        close = signer,
+        has_one = vault,
+        seeds = [b"delegate", vault.key().as_ref(), vault_delegate.delegate.as_ref()],
+        bump,
+    )]
+    pub vault_delegate: Account<'info, VaultDelegate>,
+
+    // The signer's ownership token, for tokenized vaults
+    pub ownership_token: Option<Account<'info, TokenAccount>>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct DelegateWithdraw<'info> {
+    // The delegate key - not the vault owner
+    pub signer: Signer<'info>,
+
+    // Delegates are suspended once a multisig controls the vault
+    #[account(
+        mut,
+        seeds = [b"vault", vault.creator.as_ref(), vault.name.as_bytes()],
+        bump,
+        constraint = !vault.multisig @ VaultError::MultisigEnabled,
+        constraint = !vault.history_attached || history.is_some() @ VaultError::HistoryRequired,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        has_one = vault,
+        seeds = [b"delegate", vault.key().as_ref(), signer.key().as_ref()],
+        bump,
+    )]
+    pub vault_delegate: Account<'info, VaultDelegate>,
+
+    // Owner by default (the token holder for tokenized vaults), or the destination
+    // configured on the delegate
+    #[account(
+        mut,
+        constraint = vault_delegate.destination.map_or(
+            vault.is_owner(&destination.key(), ownership_token.as_ref()),
+            |configured| configured == destination.key(),
+        ) @ VaultError::InvalidDestination,
+    )]
+    pub destination: SystemAccount<'info>,
+
+    // The vault's history account, to record this operation in it - required once attached
+    #[account(
+        mut,
+        seeds = [b"history", vault.key().as_ref()],
+        bump,
+    )]
+    pub history: Option<AccountLoader<'info, VaultHistory>>,
+
+    // The destination's ownership token, when paying a tokenized vault's owner
+    pub ownership_token: Option<Account<'info, TokenAccount>>,
+
+    // The recipient's allowlist entry - required while the vault enforces its allowlist
+    pub allowed_destination: Option<Account<'info, AllowedDestination>>,
+}
+
+#[derive(Accounts)]
+pub struct CreateMultisig<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", vault.creator.as_ref(), vault.name.as_bytes()],
+        bump,
+        constraint = vault.is_owner(&signer.key(), ownership_token.as_ref()) @ VaultError::Unauthorized,
+        constraint = !vault.multisig @ VaultError::MultisigEnabled,
+        // Stake can only be deactivated and withdrawn by the owner, so it must be
+        // wound down before the owner hands over control
+        constraint = vault.staked_lamports == 0 @ VaultError::StakeOutstanding,
+        // Proposals pay out without allowlist entries, and only the owner can turn it off
+        constraint = !vault.allowlist_enforced @ VaultError::AllowlistEnforced,
+        // Proposals only move lamports, so tokens must be withdrawn first
+        constraint = vault.token_positions == 0 @ VaultError::TokenPositionsOutstanding,
+        // Grants keep paying out after the handover and only the owner could revoke them
+        constraint = vault.grant_count == 0 @ VaultError::GrantsOutstanding,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        init,
+        payer = signer,
+        space = VaultMultisig::INIT_SPACE + VaultMultisig::DISCRIMINATOR.len(),
+        seeds = [b"multisig", vault.key().as_ref()],
+        bump,
+    )]
+    pub multisig: Account<'info, VaultMultisig>,
+
+    pub system_program: Program<'info, System>,
+
+    // The signer's ownership token, for tokenized vaults
+    pub ownership_token: Option<Account<'info, TokenAccount>>,
+}
+
+#[derive(Accounts)]
+pub struct CreateProposal<'info> {
+    // Must be a multisig member - also pays for the proposal account
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"multisig", multisig.vault.as_ref()],
+        bump,
+    )]
+    pub multisig: Account<'info, VaultMultisig>,
+
+    #[account(
+        init,
+        payer = signer,
+        space = MultisigProposal::INIT_SPACE + MultisigProposal::DISCRIMINATOR.len(),
+        seeds = [b"proposal", multisig.key().as_ref(), &multisig.proposal_count.to_le_bytes()],
+        bump,
+    )]
+    pub proposal: Account<'info, MultisigProposal>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ApproveProposal<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(
+        seeds = [b"multisig", multisig.vault.as_ref()],
+        bump,
+    )]
+    pub multisig: Account<'info, VaultMultisig>,
+
+    #[account(
+        mut,
+        has_one = multisig,
+        seeds = [b"proposal", multisig.key().as_ref(), &proposal.index.to_le_bytes()],
+        bump,
+    )]
+    pub proposal: Account<'info, MultisigProposal>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ExecuteProposal<'info> {
+    // Anyone can execute once the threshold is met
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", vault.creator.as_ref(), vault.name.as_bytes()],
+        bump,
+        constraint = !vault.history_attached || history.is_some() @ VaultError::HistoryRequired,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        has_one = vault,
+        seeds = [b"multisig", vault.key().as_ref()],
+        bump,
+    )]
+    pub multisig: Account<'info, VaultMultisig>,
+
+    #[account(
+        mut,
+        has_one = multisig,
+        seeds = [b"proposal", multisig.key().as_ref(), &proposal.index.to_le_bytes()],
+        bump,
+    )]
+    pub proposal: Account<'info, MultisigProposal>,
+
+    /// CHECK: Only used by Withdraw proposals, where it must match the proposal's recipient
+    #[account(mut)]
+    pub recipient: Option<UncheckedAccount<'info>>,
+
+    // The vault's history account, to record this operation in it - required once attached
+    #[account(
+        mut,
+        seeds = [b"history", vault.key().as_ref()],
+        bump,
+    )]
+    pub history: Option<AccountLoader<'info, VaultHistory>>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CloseVault<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    // `close = recipient` moves the whole balance (rent included) and closes the account
+    #[account(
+        mut,
+        seeds = [b"vault", vault.creator.as_ref(), vault.name.as_bytes()],
+        bump,
+        constraint = vault.is_owner(&signer.key(), ownership_token.as_ref()) @ VaultError::Unauthorized,
+        close = recipient,
+        constraint = !vault.multisig @ VaultError::MultisigEnabled,
+        constraint = vault.name.is_empty() || owner_index.is_some() @ VaultError::OwnerIndexRequired,
+        constraint = !vault.history_attached || history.is_some() @ VaultError::HistoryRequired,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    /// CHECK: Any account can receive the closing balance - usually the owner
+    #[account(
+        mut,
+        constraint = recipient.key() != vault.key() @ VaultError::InvalidDestination,
+    )]
+    pub recipient: UncheckedAccount<'info>,
+
+    // The creator's index, to drop the closed vault from it - required for named vaults,
+    // which are always listed there (the default vault never is)
+    #[account(
+        mut,
+        seeds = [b"owner_index", vault.creator.as_ref()],
+        bump,
+    )]
+    pub owner_index: Option<Account<'info, OwnerIndex>>,
+
+    // Closed together with the vault - required once attached
+    #[account(
+        mut,
+        close = recipient,
+        seeds = [b"history", vault.key().as_ref()],
+        bump,
+    )]
+    pub history: Option<AccountLoader<'info, VaultHistory>>,
+
+    // The signer's ownership token, for tokenized vaults
+    pub ownership_token: Option<Account<'info, TokenAccount>>,
+
+    // The recipient's allowlist entry - required while the vault enforces its allowlist
+    pub allowed_destination: Option<Account<'info, AllowedDestination>>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeOwner<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", vault.creator.as_ref(), vault.name.as_bytes()],
+        bump,
+        constraint = vault.is_owner(&signer.key(), ownership_token.as_ref()) @ VaultError::Unauthorized,
+        // Key-bound grants would outlive a sale of the ownership token
+        constraint = !vault.tokenized @ VaultError::VaultTokenized,
+        constraint = !vault.multisig @ VaultError::MultisigEnabled,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    // The signer's ownership token, for tokenized vaults
+    pub ownership_token: Option<Account<'info, TokenAccount>>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct AcceptOwner<'info> {
+    // The proposed new owner
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", vault.creator.as_ref(), vault.name.as_bytes()],
+        bump,
+        constraint = vault.pending_owner == Some(signer.key()) @ VaultError::Unauthorized,
+        constraint = !vault.multisig @ VaultError::MultisigEnabled,
+    )]
+    pub vault: Account<'info, Vault>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct WithdrawalLimitAction<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", vault.creator.as_ref(), vault.name.as_bytes()],
+        bump,
+        constraint = vault.is_owner(&signer.key(), ownership_token.as_ref()) @ VaultError::Unauthorized,
+        constraint = !vault.multisig @ VaultError::MultisigEnabled,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    // The signer's ownership token, for tokenized vaults
+    pub ownership_token: Option<Account<'info, TokenAccount>>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeOwnerIndex<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        init,
+        payer = signer,
+        space = OwnerIndex::INIT_SPACE + OwnerIndex::DISCRIMINATOR.len(),
+        seeds = [b"owner_index", signer.key().as_ref()],
+        bump,
+    )]
+    pub owner_index: Account<'info, OwnerIndex>,
 
     pub system_program: Program<'info, System>,
 }
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(name: String)]
+pub struct InitializeNamedVault<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    // Anchor derives this PDA before any name check can run, and deriving it from a seed
+    // over 32 bytes would panic. The seed is clamped so derivation always succeeds;
+    // `validate_name` then rejects the over-long name and the whole transaction reverts.
+    #[account(
+        init,
+        payer = signer,
+        space = Vault::INIT_SPACE + Vault::DISCRIMINATOR.len(),
+        seeds = [b"vault", signer.key().as_ref(), &name.as_bytes()[..name.len().min(MAX_VAULT_NAME_LEN)]],
+        bump,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [b"owner_index", signer.key().as_ref()],
+        bump,
+    )]
+    pub owner_index: Account<'info, OwnerIndex>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct DepositTo<'info> {
+    // Any payer - does not need to be the vault owner
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", vault.creator.as_ref(), vault.name.as_bytes()],
+        bump,
+        constraint = !vault.history_attached || history.is_some() @ VaultError::HistoryRequired,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = DepositorReceipt::INIT_SPACE + DepositorReceipt::DISCRIMINATOR.len(),
+        seeds = [b"receipt", vault.key().as_ref(), signer.key().as_ref()],
+        bump,
+    )]
+    pub depositor_receipt: Account<'info, DepositorReceipt>,
+
+    pub system_program: Program<'info, System>,
+
+    // The vault's history account, to record this operation in it - required once attached
+    #[account(
+        mut,
+        seeds = [b"history", vault.key().as_ref()],
+        bump,
+    )]
+    pub history: Option<AccountLoader<'info, VaultHistory>>,
+}
+
+#[derive(Accounts)]
+pub struct CreateStream<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", vault.creator.as_ref(), vault.name.as_bytes()],
+        bump,
+        constraint = vault.is_owner(&signer.key(), ownership_token.as_ref()) @ VaultError::Unauthorized,
+        // Key-bound grants would outlive a sale of the ownership token
+        constraint = !vault.tokenized @ VaultError::VaultTokenized,
+        constraint = !vault.multisig @ VaultError::MultisigEnabled,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    pub recipient: SystemAccount<'info>,
+
+    #[account(
+        init,
+        payer = signer,
+        space = Stream::INIT_SPACE + Stream::DISCRIMINATOR.len(),
+        seeds = [b"stream", vault.key().as_ref(), recipient.key().as_ref()],
+        bump,
+    )]
+    pub stream: Account<'info, Stream>,
+
+    pub system_program: Program<'info, System>,
+
+    // The signer's ownership token, for tokenized vaults
+    pub ownership_token: Option<Account<'info, TokenAccount>>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClaimStream<'info> {
+    // The stream recipient
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", vault.creator.as_ref(), vault.name.as_bytes()],
+        bump,
+        constraint = !vault.history_attached || history.is_some() @ VaultError::HistoryRequired,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        has_one = vault,
+        seeds = [b"stream", vault.key().as_ref(), signer.key().as_ref()],
+        bump,
+    )]
+    pub stream: Account<'info, Stream>,
+
+    // The vault's history account, to record this operation in it - required once attached
+    #[account(
+        mut,
+        seeds = [b"history", vault.key().as_ref()],
+        bump,
+    )]
+    pub history: Option<AccountLoader<'info, VaultHistory>>,
+
+    // The recipient's allowlist entry - required while the vault enforces its allowlist
+    pub allowed_destination: Option<Account<'info, AllowedDestination>>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CancelStream<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", vault.creator.as_ref(), vault.name.as_bytes()],
+        bump,
+        constraint = vault.is_owner(&signer.key(), ownership_token.as_ref()) @ VaultError::Unauthorized,
+        constraint = !vault.multisig @ VaultError::MultisigEnabled,
+        constraint = !vault.history_attached || history.is_some() @ VaultError::HistoryRequired,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        close = signer,
+        has_one = vault,
+        has_one = recipient,
+        seeds = [b"stream", vault.key().as_ref(), recipient.key().as_ref()],
+        bump,
+    )]
+    pub stream: Account<'info, Stream>,
+
+    // Receives the vested, unclaimed part on cancellation
+    #[account(mut)]
+    pub recipient: SystemAccount<'info>,
+
+    // The vault's history account, to record this operation in it - required once attached
+    #[account(
+        mut,
+        seeds = [b"history", vault.key().as_ref()],
+        bump,
+    )]
+    pub history: Option<AccountLoader<'info, VaultHistory>>,
+
+    // The signer's ownership token, for tokenized vaults
+    pub ownership_token: Option<Account<'info, TokenAccount>>,
+
+    // The recipient's allowlist entry - required while the vault enforces its allowlist
+    pub allowed_destination: Option<Account<'info, AllowedDestination>>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeHistory<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", vault.creator.as_ref(), vault.name.as_bytes()],
+        bump,
+        constraint = vault.is_owner(&signer.key(), ownership_token.as_ref()) @ VaultError::Unauthorized,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        init,
+        payer = signer,
+        space = VaultHistory::INIT_SPACE + VaultHistory::DISCRIMINATOR.len(),
+        seeds = [b"history", vault.key().as_ref()],
+        bump,
+    )]
+    pub history: AccountLoader<'info, VaultHistory>,
+
+    pub system_program: Program<'info, System>,
+
+    // The signer's ownership token, for tokenized vaults
+    pub ownership_token: Option<Account<'info, TokenAccount>>,
+}
+
+#[derive(Accounts)]
+#[instruction(stake_id: u64)]
+pub struct DelegateStake<'info> {
+    // Owner - also pays the stake account's rent-exempt reserve and the record's rent
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", vault.creator.as_ref(), vault.name.as_bytes()],
+        bump,
+        constraint = vault.is_owner(&signer.key(), ownership_token.as_ref()) @ VaultError::Unauthorized,
+        constraint = !vault.multisig @ VaultError::MultisigEnabled,
+        constraint = !vault.pooled @ VaultError::VaultPooled,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    /// CHECK: Created in the handler as a Stake program account at this PDA
+    #[account(
+        mut,
+        seeds = [b"stake", vault.key().as_ref(), &stake_id.to_le_bytes()],
+        bump,
+    )]
+    pub stake_account: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = signer,
+        space = VaultStake::INIT_SPACE + VaultStake::DISCRIMINATOR.len(),
+        seeds = [b"vault_stake", stake_account.key().as_ref()],
+        bump,
+    )]
+    pub vault_stake: Account<'info, VaultStake>,
+
+    /// CHECK: Validated by the Stake program when delegating
+    pub vote_account: UncheckedAccount<'info>,
+
+    pub clock: Sysvar<'info, Clock>,
+    pub rent: Sysvar<'info, Rent>,
+
+    /// CHECK: StakeHistory sysvar, checked by address
+    #[account(address = STAKE_HISTORY_SYSVAR_ID)]
+    pub stake_history: UncheckedAccount<'info>,
+
+    /// CHECK: Stake config account (unused by the Stake program, still required), checked by address
+    #[account(address = STAKE_CONFIG_ID)]
+    pub stake_config: UncheckedAccount<'info>,
+
+    /// CHECK: Native Stake program, checked by address
+    #[account(address = solana_stake_interface::program::ID)]
+    pub stake_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    // The signer's ownership token, for tokenized vaults
+    pub ownership_token: Option<Account<'info, TokenAccount>>,
+}
+
+#[derive(Accounts)]
+pub struct DeactivateStake<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", vault.creator.as_ref(), vault.name.as_bytes()],
+        bump,
+        constraint = vault.is_owner(&signer.key(), ownership_token.as_ref()) @ VaultError::Unauthorized,
+        constraint = !vault.multisig @ VaultError::MultisigEnabled,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        has_one = vault,
+        has_one = stake_account,
+        seeds = [b"vault_stake", stake_account.key().as_ref()],
+        bump,
+    )]
+    pub vault_stake: Account<'info, VaultStake>,
+
+    /// CHECK: Stake account owned by the vault, matched against vault_stake
+    #[account(mut)]
+    pub stake_account: UncheckedAccount<'info>,
+
+    pub clock: Sysvar<'info, Clock>,
+
+    /// CHECK: Native Stake program, checked by address
+    #[account(address = solana_stake_interface::program::ID)]
+    pub stake_program: UncheckedAccount<'info>,
+
+    // The signer's ownership token, for tokenized vaults
+    pub ownership_token: Option<Account<'info, TokenAccount>>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawStake<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", vault.creator.as_ref(), vault.name.as_bytes()],
+        bump,
+        constraint = vault.is_owner(&signer.key(), ownership_token.as_ref()) @ VaultError::Unauthorized,
+        constraint = !vault.multisig @ VaultError::MultisigEnabled,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        close = signer,
+        has_one = vault,
+        has_one = stake_account,
+        seeds = [b"vault_stake", stake_account.key().as_ref()],
+        bump,
+    )]
+    pub vault_stake: Account<'info, VaultStake>,
+
+    /// CHECK: Stake account owned by the vault, matched against vault_stake
+    #[account(mut)]
+    pub stake_account: UncheckedAccount<'info>,
+
+    pub clock: Sysvar<'info, Clock>,
+
+    /// CHECK: StakeHistory sysvar, checked by address
+    #[account(address = STAKE_HISTORY_SYSVAR_ID)]
+    pub stake_history: UncheckedAccount<'info>,
+
+    /// CHECK: Native Stake program, checked by address
+    #[account(address = solana_stake_interface::program::ID)]
+    pub stake_program: UncheckedAccount<'info>,
+
+    // The signer's ownership token, for tokenized vaults
+    pub ownership_token: Option<Account<'info, TokenAccount>>,
+}
+
+#[derive(Accounts)]
+pub struct SetGuardians<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", vault.creator.as_ref(), vault.name.as_bytes()],
+        bump,
+        constraint = vault.is_owner(&signer.key(), ownership_token.as_ref()) @ VaultError::Unauthorized,
+        // Key-bound grants would outlive a sale of the ownership token
+        constraint = !vault.tokenized @ VaultError::VaultTokenized,
+        constraint = !vault.multisig @ VaultError::MultisigEnabled,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        init,
+        payer = signer,
+        space = GuardianSet::INIT_SPACE + GuardianSet::DISCRIMINATOR.len(),
+        seeds = [b"guardians", vault.key().as_ref()],
+        bump,
+    )]
+    pub guardian_set: Account<'info, GuardianSet>,
+
+    pub system_program: Program<'info, System>,
+
+    // The signer's ownership token, for tokenized vaults
+    pub ownership_token: Option<Account<'info, TokenAccount>>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveGuardians<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", vault.creator.as_ref(), vault.name.as_bytes()],
+        bump,
+        constraint = vault.is_owner(&signer.key(), ownership_token.as_ref()) @ VaultError::Unauthorized,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        close = signer,
+        has_one = vault,
+        seeds = [b"guardians", vault.key().as_ref()],
+        bump,
+    )]
+    pub guardian_set: Account<'info, GuardianSet>,
+
+    // The signer's ownership token, for tokenized vaults
+    pub ownership_token: Option<Account<'info, TokenAccount>>,
+}
+
+// Used by start_recovery and approve_recovery - the signer must be a guardian
+#[event_cpi]
+#[derive(Accounts)]
+pub struct GuardianAction<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"guardians", guardian_set.vault.as_ref()],
+        bump,
+    )]
+    pub guardian_set: Account<'info, GuardianSet>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CancelRecovery<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(
+        seeds = [b"vault", vault.creator.as_ref(), vault.name.as_bytes()],
+        bump,
+        constraint = vault.is_owner(&signer.key(), ownership_token.as_ref()) @ VaultError::Unauthorized,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        has_one = vault,
+        seeds = [b"guardians", vault.key().as_ref()],
+        bump,
+    )]
+    pub guardian_set: Account<'info, GuardianSet>,
+
+    // The signer's ownership token, for tokenized vaults
+    pub ownership_token: Option<Account<'info, TokenAccount>>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct FinishRecovery<'info> {
+    // Anyone can finish a recovery once it is approved and unchallenged
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", vault.creator.as_ref(), vault.name.as_bytes()],
+        bump,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        has_one = vault,
+        seeds = [b"guardians", vault.key().as_ref()],
+        bump,
+    )]
+    pub guardian_set: Account<'info, GuardianSet>,
+}
+
+// Used by set_beneficiary and heartbeat
+#[event_cpi]
+#[derive(Accounts)]
+pub struct InheritanceAction<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", vault.creator.as_ref(), vault.name.as_bytes()],
+        bump,
+        constraint = vault.is_owner(&signer.key(), ownership_token.as_ref()) @ VaultError::Unauthorized,
+        constraint = !vault.multisig @ VaultError::MultisigEnabled,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    // The signer's ownership token, for tokenized vaults
+    pub ownership_token: Option<Account<'info, TokenAccount>>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClaimInheritance<'info> {
+    // The beneficiary - becomes the new owner
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", vault.creator.as_ref(), vault.name.as_bytes()],
+        bump,
+        constraint = vault.beneficiary == Some(signer.key()) @ VaultError::Unauthorized,
+        constraint = !vault.multisig @ VaultError::MultisigEnabled,
+    )]
+    pub vault: Account<'info, Vault>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(authorization: WithdrawalAuthorization)]
+pub struct WithdrawSigned<'info> {
+    // Relayer submitting the owner's signed authorization - pays the transaction fee
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", vault.creator.as_ref(), vault.name.as_bytes()],
+        bump,
+        constraint = vault.key() == authorization.vault @ VaultError::Unauthorized,
+        constraint = !vault.multisig @ VaultError::MultisigEnabled,
+        constraint = !vault.history_attached || history.is_some() @ VaultError::HistoryRequired,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    /// CHECK: Must be the recipient the owner signed for
+    #[account(
+        mut,
+        constraint = recipient.key() == authorization.recipient @ VaultError::InvalidDestination,
+        constraint = recipient.key() != vault.key() @ VaultError::InvalidDestination,
+    )]
+    pub recipient: UncheckedAccount<'info>,
+
+    /// CHECK: Instructions sysvar, used to find the Ed25519 instruction before this one
+    #[account(address = INSTRUCTIONS_SYSVAR_ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    // The vault's history account, to record this operation in it - required once attached
+    #[account(
+        mut,
+        seeds = [b"history", vault.key().as_ref()],
+        bump,
+    )]
+    pub history: Option<AccountLoader<'info, VaultHistory>>,
+
+    // The authorizing key's ownership token, for tokenized vaults
+    pub ownership_token: Option<Account<'info, TokenAccount>>,
+
+    // The recipient's allowlist entry - required while the vault enforces its allowlist
+    pub allowed_destination: Option<Account<'info, AllowedDestination>>,
+}
+
+#[derive(Accounts)]
+#[instruction(session_key: Pubkey)]
+pub struct CreateSession<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", vault.creator.as_ref(), vault.name.as_bytes()],
+        bump,
+        constraint = vault.is_owner(&signer.key(), ownership_token.as_ref()) @ VaultError::Unauthorized,
+        // Key-bound grants would outlive a sale of the ownership token
+        constraint = !vault.tokenized @ VaultError::VaultTokenized,
+        constraint = !vault.multisig @ VaultError::MultisigEnabled,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        init,
+        payer = signer,
+        space = VaultSession::INIT_SPACE + VaultSession::DISCRIMINATOR.len(),
+        seeds = [b"session", vault.key().as_ref(), session_key.as_ref()],
+        bump,
+    )]
+    pub vault_session: Account<'info, VaultSession>,
+
+    pub system_program: Program<'info, System>,
+
+    // The signer's ownership token, for tokenized vaults
+    pub ownership_token: Option<Account<'info, TokenAccount>>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeSession<'info> {
+    // The owner at any time, or anyone once the session has expired
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", vault.creator.as_ref(), vault.name.as_bytes()],
+        bump,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    // The session's rent always goes back to the owner (the token holder for tokenized vaults)
+    #[account(
+        mut,
+        constraint = vault.is_owner(&owner.key(), ownership_token.as_ref()) @ VaultError::InvalidDestination,
+    )]
+    pub owner: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        close = owner,
+        has_one = vault,
+        seeds = [b"session", vault.key().as_ref(), vault_session.session_key.as_ref()],
+        bump,
+    )]
+    pub vault_session: Account<'info, VaultSession>,
+
+    // The owner's ownership token, for tokenized vaults
+    pub ownership_token: Option<Account<'info, TokenAccount>>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SessionDeposit<'info> {
+    // The session key - pays the deposit from its own balance
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", vault.creator.as_ref(), vault.name.as_bytes()],
+        bump,
+        constraint = !vault.history_attached || history.is_some() @ VaultError::HistoryRequired,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        has_one = vault,
+        seeds = [b"session", vault.key().as_ref(), signer.key().as_ref()],
+        bump,
+    )]
+    pub vault_session: Account<'info, VaultSession>,
+
+    pub system_program: Program<'info, System>,
+
+    // The vault's history account, to record this operation in it - required once attached
+    #[account(
+        mut,
+        seeds = [b"history", vault.key().as_ref()],
+        bump,
+    )]
+    pub history: Option<AccountLoader<'info, VaultHistory>>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SessionWithdraw<'info> {
+    // The session key
+    pub signer: Signer<'info>,
+
+    // Sessions are suspended once a multisig controls the vault
+    #[account(
+        mut,
+        seeds = [b"vault", vault.creator.as_ref(), vault.name.as_bytes()],
+        bump,
+        constraint = !vault.multisig @ VaultError::MultisigEnabled,
+        constraint = !vault.history_attached || history.is_some() @ VaultError::HistoryRequired,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        has_one = vault,
+        seeds = [b"session", vault.key().as_ref(), signer.key().as_ref()],
+        bump,
+    )]
+    pub vault_session: Account<'info, VaultSession>,
+
+    /// CHECK: Checked against the session's fixed destination in the handler
+    #[account(mut)]
+    pub destination: UncheckedAccount<'info>,
+
+    // The vault's history account, to record this operation in it - required once attached
+    #[account(
+        mut,
+        seeds = [b"history", vault.key().as_ref()],
+        bump,
+    )]
+    pub history: Option<AccountLoader<'info, VaultHistory>>,
+
+    // The recipient's allowlist entry - required while the vault enforces its allowlist
+    pub allowed_destination: Option<Account<'info, AllowedDestination>>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct MigrateVault<'info> {
+    // The vault owner - pays for the extra rent
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /// CHECK: An old layout does not deserialize as Vault, so the handler checks the program
+    /// owner and discriminator itself and reads the owner from the old layout
+    #[account(mut)]
+    pub vault: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    // The signer's ownership token, for tokenized vaults
+    pub ownership_token: Option<Account<'info, TokenAccount>>,
+}
+
+#[derive(Accounts)]
+pub struct EnablePooling<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", vault.creator.as_ref(), vault.name.as_bytes()],
+        bump,
+        constraint = vault.is_owner(&signer.key(), ownership_token.as_ref()) @ VaultError::Unauthorized,
+        constraint = !vault.multisig @ VaultError::MultisigEnabled,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    // The signer's ownership token, for tokenized vaults
+    pub ownership_token: Option<Account<'info, TokenAccount>>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct PoolDeposit<'info> {
+    // Any depositor - pays for their shares account on the first deposit
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", vault.creator.as_ref(), vault.name.as_bytes()],
+        bump,
+        constraint = vault.pooled @ VaultError::VaultNotPooled,
+        constraint = !vault.history_attached || history.is_some() @ VaultError::HistoryRequired,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = PoolShares::INIT_SPACE + PoolShares::DISCRIMINATOR.len(),
+        seeds = [b"shares", vault.key().as_ref(), signer.key().as_ref()],
+        bump,
+    )]
+    pub pool_shares: Account<'info, PoolShares>,
+
+    pub system_program: Program<'info, System>,
+
+    // The vault's history account, to record this operation in it - required once attached
+    #[account(
+        mut,
+        seeds = [b"history", vault.key().as_ref()],
+        bump,
+    )]
+    pub history: Option<AccountLoader<'info, VaultHistory>>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct PoolWithdraw<'info> {
+    // The depositor - receives the lamports their shares are worth
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", vault.creator.as_ref(), vault.name.as_bytes()],
+        bump,
+        constraint = vault.pooled @ VaultError::VaultNotPooled,
+        constraint = !vault.history_attached || history.is_some() @ VaultError::HistoryRequired,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        has_one = vault,
+        seeds = [b"shares", vault.key().as_ref(), signer.key().as_ref()],
+        bump,
+    )]
+    pub pool_shares: Account<'info, PoolShares>,
+
+    // The vault's history account, to record this operation in it - required once attached
+    #[account(
+        mut,
+        seeds = [b"history", vault.key().as_ref()],
+        bump,
+    )]
+    pub history: Option<AccountLoader<'info, VaultHistory>>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(destination: Pubkey)]
+pub struct AddDestination<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", vault.creator.as_ref(), vault.name.as_bytes()],
+        bump,
+        constraint = vault.is_owner(&signer.key(), ownership_token.as_ref()) @ VaultError::Unauthorized,
+        constraint = !vault.multisig @ VaultError::MultisigEnabled,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        init,
+        payer = signer,
+        space = AllowedDestination::INIT_SPACE + AllowedDestination::DISCRIMINATOR.len(),
+        seeds = [b"destination", vault.key().as_ref(), destination.as_ref()],
+        bump,
+    )]
+    pub allowed_destination: Account<'info, AllowedDestination>,
+
+    pub system_program: Program<'info, System>,
+
+    // The signer's ownership token, for tokenized vaults
+    pub ownership_token: Option<Account<'info, TokenAccount>>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RemoveDestination<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", vault.creator.as_ref(), vault.name.as_bytes()],
+        bump,
+        constraint = vault.is_owner(&signer.key(), ownership_token.as_ref()) @ VaultError::Unauthorized,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        close = signer,
+        has_one = vault,
+        seeds = [b"destination", vault.key().as_ref(), allowed_destination.destination.as_ref()],
+        bump,
+    )]
+    pub allowed_destination: Account<'info, AllowedDestination>,
+
+    // The signer's ownership token, for tokenized vaults
+    pub ownership_token: Option<Account<'info, TokenAccount>>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct WithdrawTo<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", vault.creator.as_ref(), vault.name.as_bytes()],
+        bump,
+        constraint = vault.is_owner(&signer.key(), ownership_token.as_ref()) @ VaultError::Unauthorized,
+        constraint = !vault.multisig @ VaultError::MultisigEnabled,
+        constraint = !vault.history_attached || history.is_some() @ VaultError::HistoryRequired,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    /// CHECK: Any address, as long as it is on the vault's allowlist
+    #[account(mut)]
+    pub recipient: UncheckedAccount<'info>,
+
+    // Only exists for allowlisted recipients
+    #[account(
+        has_one = vault,
+        seeds = [b"destination", vault.key().as_ref(), recipient.key().as_ref()],
+        bump,
+    )]
+    pub allowed_destination: Account<'info, AllowedDestination>,
+
+    // The vault's history account, to record this operation in it - required once attached
+    #[account(
+        mut,
+        seeds = [b"history", vault.key().as_ref()],
+        bump,
+    )]
+    pub history: Option<AccountLoader<'info, VaultHistory>>,
+
+    // The signer's ownership token, for tokenized vaults
+    pub ownership_token: Option<Account<'info, TokenAccount>>,
+}
+
+#[derive(Accounts)]
+pub struct CreateGovernance<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        seeds = [b"vault", vault.creator.as_ref(), vault.name.as_bytes()],
+        bump,
+        constraint = vault.is_owner(&signer.key(), ownership_token.as_ref()) @ VaultError::Unauthorized,
+        constraint = !vault.multisig @ VaultError::MultisigEnabled,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        init,
+        payer = signer,
+        space = Governance::INIT_SPACE + Governance::DISCRIMINATOR.len(),
+        seeds = [b"governance", vault.key().as_ref()],
+        bump,
+    )]
+    pub governance: Account<'info, Governance>,
+
+    pub system_program: Program<'info, System>,
+
+    // The signer's ownership token, for tokenized vaults
+    pub ownership_token: Option<Account<'info, TokenAccount>>,
+}
+
+#[derive(Accounts)]
+#[instruction(member: Pubkey)]
+pub struct AddGovernanceMember<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        seeds = [b"vault", vault.creator.as_ref(), vault.name.as_bytes()],
+        bump,
+        constraint = vault.is_owner(&signer.key(), ownership_token.as_ref()) @ VaultError::Unauthorized,
+        constraint = !vault.multisig @ VaultError::MultisigEnabled,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        has_one = vault,
+        seeds = [b"governance", vault.key().as_ref()],
+        bump,
+    )]
+    pub governance: Account<'info, Governance>,
+
+    #[account(
+        init,
+        payer = signer,
+        space = GovernanceMember::INIT_SPACE + GovernanceMember::DISCRIMINATOR.len(),
+        seeds = [b"member", governance.key().as_ref(), member.as_ref()],
+        bump,
+    )]
+    pub governance_member: Account<'info, GovernanceMember>,
+
+    pub system_program: Program<'info, System>,
+
+    // The signer's ownership token, for tokenized vaults
+    pub ownership_token: Option<Account<'info, TokenAccount>>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ActivateGovernance<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", vault.creator.as_ref(), vault.name.as_bytes()],
+        bump,
+        constraint = vault.is_owner(&signer.key(), ownership_token.as_ref()) @ VaultError::Unauthorized,
+        constraint = !vault.multisig @ VaultError::MultisigEnabled,
+        // Stake can only be deactivated and withdrawn by the owner, so it must be
+        // wound down before the owner hands over control
+        constraint = vault.staked_lamports == 0 @ VaultError::StakeOutstanding,
+        // Proposals pay out without allowlist entries, and only the owner can turn it off
+        constraint = !vault.allowlist_enforced @ VaultError::AllowlistEnforced,
+        // Proposals only move lamports, so tokens must be withdrawn first
+        constraint = vault.token_positions == 0 @ VaultError::TokenPositionsOutstanding,
+        // Grants keep paying out after the handover and only the owner could revoke them
+        constraint = vault.grant_count == 0 @ VaultError::GrantsOutstanding,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        has_one = vault,
+        seeds = [b"governance", vault.key().as_ref()],
+        bump,
+    )]
+    pub governance: Account<'info, Governance>,
+
+    // The signer's ownership token, for tokenized vaults
+    pub ownership_token: Option<Account<'info, TokenAccount>>,
+}
+
+#[derive(Accounts)]
+pub struct CreateSpendProposal<'info> {
+    // Anyone can propose - also pays for the proposal account
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"governance", governance.vault.as_ref()],
+        bump,
+    )]
+    pub governance: Account<'info, Governance>,
+
+    #[account(
+        init,
+        payer = signer,
+        space = SpendProposal::INIT_SPACE + SpendProposal::DISCRIMINATOR.len(),
+        seeds = [b"spend_proposal", governance.key().as_ref(), &governance.proposal_count.to_le_bytes()],
+        bump,
+    )]
+    pub proposal: Account<'info, SpendProposal>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CastVote<'info> {
+    // The member - pays for the vote record
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        seeds = [b"governance", governance.vault.as_ref()],
+        bump,
+    )]
+    pub governance: Account<'info, Governance>,
+
+    #[account(
+        has_one = governance,
+        seeds = [b"member", governance.key().as_ref(), signer.key().as_ref()],
+        bump,
+    )]
+    pub governance_member: Account<'info, GovernanceMember>,
+
+    #[account(
+        mut,
+        has_one = governance,
+        seeds = [b"spend_proposal", governance.key().as_ref(), &proposal.index.to_le_bytes()],
+        bump,
+    )]
+    pub proposal: Account<'info, SpendProposal>,
+
+    #[account(
+        init,
+        payer = signer,
+        space = VoteRecord::INIT_SPACE + VoteRecord::DISCRIMINATOR.len(),
+        seeds = [b"vote", proposal.key().as_ref(), signer.key().as_ref()],
+        bump,
+    )]
+    pub vote_record: Account<'info, VoteRecord>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ExecuteSpendProposal<'info> {
+    // Anyone can execute a passed proposal once its delay is over
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", vault.creator.as_ref(), vault.name.as_bytes()],
+        bump,
+        constraint = !vault.history_attached || history.is_some() @ VaultError::HistoryRequired,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        has_one = vault,
+        seeds = [b"governance", vault.key().as_ref()],
+        bump,
+    )]
+    pub governance: Account<'info, Governance>,
+
+    #[account(
+        mut,
+        has_one = governance,
+        seeds = [b"spend_proposal", governance.key().as_ref(), &proposal.index.to_le_bytes()],
+        bump,
+    )]
+    pub proposal: Account<'info, SpendProposal>,
+
+    /// CHECK: Must be the proposal's recipient
+    #[account(
+        mut,
+        address = proposal.recipient @ VaultError::InvalidDestination,
+    )]
+    pub recipient: UncheckedAccount<'info>,
+
+    // The vault's history account, to record this operation in it - required once attached
+    #[account(
+        mut,
+        seeds = [b"history", vault.key().as_ref()],
+        bump,
+    )]
+    pub history: Option<AccountLoader<'info, VaultHistory>>,
+}
+
+#[derive(Accounts)]
+pub struct CreateSubscription<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", vault.creator.as_ref(), vault.name.as_bytes()],
+        bump,
+        constraint = vault.is_owner(&signer.key(), ownership_token.as_ref()) @ VaultError::Unauthorized,
+        // Key-bound grants would outlive a sale of the ownership token
+        constraint = !vault.tokenized @ VaultError::VaultTokenized,
+        constraint = !vault.multisig @ VaultError::MultisigEnabled,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    pub merchant: SystemAccount<'info>,
+
+    #[account(
+        init,
+        payer = signer,
+        space = Subscription::INIT_SPACE + Subscription::DISCRIMINATOR.len(),
+        seeds = [b"subscription", vault.key().as_ref(), merchant.key().as_ref()],
+        bump,
+    )]
+    pub subscription: Account<'info, Subscription>,
+
+    pub system_program: Program<'info, System>,
+
+    // The signer's ownership token, for tokenized vaults
+    pub ownership_token: Option<Account<'info, TokenAccount>>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CollectSubscription<'info> {
+    // The merchant
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", vault.creator.as_ref(), vault.name.as_bytes()],
+        bump,
+        constraint = !vault.history_attached || history.is_some() @ VaultError::HistoryRequired,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        has_one = vault,
+        seeds = [b"subscription", vault.key().as_ref(), signer.key().as_ref()],
+        bump,
+    )]
+    pub subscription: Account<'info, Subscription>,
+
+    // The vault's history account, to record this operation in it - required once attached
+    #[account(
+        mut,
+        seeds = [b"history", vault.key().as_ref()],
+        bump,
+    )]
+    pub history: Option<AccountLoader<'info, VaultHistory>>,
+
+    // The recipient's allowlist entry - required while the vault enforces its allowlist
+    pub allowed_destination: Option<Account<'info, AllowedDestination>>,
+}
+
+#[derive(Accounts)]
+pub struct CancelSubscription<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", vault.creator.as_ref(), vault.name.as_bytes()],
+        bump,
+        constraint = vault.is_owner(&signer.key(), ownership_token.as_ref()) @ VaultError::Unauthorized,
+        constraint = !vault.multisig @ VaultError::MultisigEnabled,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        close = signer,
+        has_one = vault,
+        seeds = [b"subscription", vault.key().as_ref(), subscription.merchant.as_ref()],
+        bump,
+    )]
+    pub subscription: Account<'info, Subscription>,
+
+    // The signer's ownership token, for tokenized vaults
+    pub ownership_token: Option<Account<'info, TokenAccount>>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct TransferBetweenVaults<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", from_vault.creator.as_ref(), from_vault.name.as_bytes()],
+        bump,
+        constraint = from_vault.is_owner(&signer.key(), ownership_token.as_ref()) @ VaultError::Unauthorized,
+        constraint = !from_vault.multisig @ VaultError::MultisigEnabled,
+        constraint = !from_vault.history_attached || from_history.is_some() @ VaultError::HistoryRequired,
+    )]
+    pub from_vault: Account<'info, Vault>,
+
+    // Any other vault - pooled vaults only take deposits through pool_deposit
+    #[account(
+        mut,
+        seeds = [b"vault", to_vault.creator.as_ref(), to_vault.name.as_bytes()],
+        bump,
+        constraint = to_vault.key() != from_vault.key() @ VaultError::InvalidDestination,
+        constraint = !to_vault.pooled @ VaultError::VaultPooled,
+        constraint = !to_vault.history_attached || to_history.is_some() @ VaultError::HistoryRequired,
+    )]
+    pub to_vault: Account<'info, Vault>,
+
+    // The source vault's history account - required once attached
+    #[account(
+        mut,
+        seeds = [b"history", from_vault.key().as_ref()],
+        bump,
+    )]
+    pub from_history: Option<AccountLoader<'info, VaultHistory>>,
+
+    // The destination vault's history account - required once attached
+    #[account(
+        mut,
+        seeds = [b"history", to_vault.key().as_ref()],
+        bump,
+    )]
+    pub to_history: Option<AccountLoader<'info, VaultHistory>>,
+
+    // The signer's ownership token, for tokenized source vaults
+    pub ownership_token: Option<Account<'info, TokenAccount>>,
+
+    // The recipient's allowlist entry - required while the vault enforces its allowlist
+    pub allowed_destination: Option<Account<'info, AllowedDestination>>,
+}
+
+// Token custody works with both the SPL Token and Token-2022 programs, so token accounts
+// and mints go through `token_interface`
+#[event_cpi]
+#[derive(Accounts)]
+pub struct DepositToken<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", vault.creator.as_ref(), vault.name.as_bytes()],
+        bump,
+        constraint = vault.is_owner(&signer.key(), ownership_token.as_ref()) @ VaultError::Unauthorized,
+        constraint = !vault.pooled @ VaultError::VaultPooled,
+        // Multisig and governance proposals only move lamports, so tokens could not leave
+        constraint = !vault.multisig @ VaultError::MultisigEnabled,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    // A tokenized vault's own ownership token would lock the vault out of itself
+    #[account(
+        mint::token_program = token_program,
+        constraint = mint.key() != vault.owner @ VaultError::OwnershipTokenDeposit,
+    )]
+    pub mint: InterfaceAccount<'info, token_interface::Mint>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = signer,
+        token::token_program = token_program,
+    )]
+    pub signer_token: InterfaceAccount<'info, token_interface::TokenAccount>,
+
+    // The vault's associated token account for this mint, created on the first deposit
+    #[account(
+        init_if_needed,
+        payer = signer,
+        associated_token::mint = mint,
+        associated_token::authority = vault,
+        associated_token::token_program = token_program,
+    )]
+    pub vault_token: InterfaceAccount<'info, token_interface::TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = signer,
+        space = VaultTokenTotals::INIT_SPACE + VaultTokenTotals::DISCRIMINATOR.len(),
+        seeds = [b"vault_token", vault.key().as_ref(), mint.key().as_ref()],
+        bump,
+    )]
+    pub totals: Account<'info, VaultTokenTotals>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+
+    // The signer's ownership token, for tokenized vaults
+    pub ownership_token: Option<Account<'info, TokenAccount>>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct WithdrawToken<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", vault.creator.as_ref(), vault.name.as_bytes()],
+        bump,
+        constraint = vault.is_owner(&signer.key(), ownership_token.as_ref()) @ VaultError::Unauthorized,
+        constraint = !vault.multisig @ VaultError::MultisigEnabled,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(mint::token_program = token_program)]
+    pub mint: InterfaceAccount<'info, token_interface::Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = vault,
+        associated_token::token_program = token_program,
+    )]
+    pub vault_token: InterfaceAccount<'info, token_interface::TokenAccount>,
+
+    // Receives the tokens - any token account of this mint
+    #[account(
+        mut,
+        token::mint = mint,
+        token::token_program = token_program,
+    )]
+    pub recipient_token: InterfaceAccount<'info, token_interface::TokenAccount>,
+
+    #[account(
+        mut,
+        has_one = vault,
+        has_one = mint,
+        seeds = [b"vault_token", vault.key().as_ref(), mint.key().as_ref()],
+        bump,
+    )]
+    pub totals: Account<'info, VaultTokenTotals>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+
+    // The signer's ownership token, for tokenized vaults
+    pub ownership_token: Option<Account<'info, TokenAccount>>,
+
+    // Allowlist entry for the recipient token account's owner - required while the vault
+    // enforces its allowlist
+    pub allowed_destination: Option<Account<'info, AllowedDestination>>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct AllowlistAction<'info> {
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", vault.creator.as_ref(), vault.name.as_bytes()],
+        bump,
+        constraint = vault.is_owner(&signer.key(), ownership_token.as_ref()) @ VaultError::Unauthorized,
+        constraint = !vault.multisig @ VaultError::MultisigEnabled,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    // The signer's ownership token, for tokenized vaults
+    pub ownership_token: Option<Account<'info, TokenAccount>>,
+}
+
+#[derive(Accounts)]
+pub struct CloseTokenAccount<'info> {
+    // Owner - receives the rent of both accounts
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"vault", vault.creator.as_ref(), vault.name.as_bytes()],
+        bump,
+        constraint = vault.is_owner(&signer.key(), ownership_token.as_ref()) @ VaultError::Unauthorized,
+        constraint = !vault.multisig @ VaultError::MultisigEnabled,
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(mint::token_program = token_program)]
+    pub mint: InterfaceAccount<'info, token_interface::Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = vault,
+        associated_token::token_program = token_program,
+        constraint = vault_token.amount == 0 @ VaultError::TokenAccountNotEmpty,
+    )]
+    pub vault_token: InterfaceAccount<'info, token_interface::TokenAccount>,
+
+    #[account(
+        mut,
+        close = signer,
+        has_one = vault,
+        has_one = mint,
+        seeds = [b"vault_token", vault.key().as_ref(), mint.key().as_ref()],
+        bump,
+    )]
+    pub totals: Account<'info, VaultTokenTotals>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+
+    // The signer's ownership token, for tokenized vaults
+    pub ownership_token: Option<Account<'info, TokenAccount>>,
+}
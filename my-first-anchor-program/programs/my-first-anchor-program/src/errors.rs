@@ -6,4 +6,136 @@ pub enum VaultError {
     VaultAlreadyExists,
     #[msg("Invalid amount")]
     InvalidAmount,
+    #[msg("Vault is locked")]
+    VaultLocked,
+    #[msg("Vault already has an active lock")]
+    LockAlreadyActive,
+    #[msg("Unlock point must be in the future")]
+    InvalidLock,
+    #[msg("Lock can only be extended to a later point of the same kind")]
+    LockNotExtended,
+    #[msg("Invalid delegate configuration")]
+    InvalidDelegateConfig,
+    #[msg("Delegate has expired")]
+    DelegateExpired,
+    #[msg("Delegate spending cap exceeded")]
+    DelegateCapExceeded,
+    #[msg("Destination does not match the configured destination")]
+    InvalidDestination,
+    #[msg("Vault is controlled by its multisig or governance")]
+    MultisigEnabled,
+    #[msg("Invalid multisig configuration")]
+    InvalidMultisigConfig,
+    #[msg("Signer is not a multisig member")]
+    NotAMultisigMember,
+    #[msg("Member has already approved this proposal")]
+    AlreadyApproved,
+    #[msg("Proposal has already been executed")]
+    ProposalAlreadyExecuted,
+    #[msg("Proposal does not have enough approvals")]
+    NotEnoughApprovals,
+    #[msg("Revoke all delegates, streams, subscriptions, sessions, guardians and allowlist destinations first")]
+    GrantsOutstanding,
+    #[msg("Signer is not authorized for this vault")]
+    Unauthorized,
+    #[msg("Invalid withdrawal limit")]
+    InvalidWithdrawalLimit,
+    #[msg("Withdrawal limit exceeded for the current window")]
+    WithdrawalLimitExceeded,
+    #[msg("No pending withdrawal limit change")]
+    NoPendingLimitChange,
+    #[msg("Withdrawal limit change is still timelocked")]
+    LimitChangeTimelocked,
+    #[msg("Vault name must be 1 to 32 bytes")]
+    InvalidVaultName,
+    #[msg("Owner index is full")]
+    OwnerIndexFull,
+    #[msg("Invalid stream configuration")]
+    InvalidStreamConfig,
+    #[msg("Nothing has vested yet")]
+    NothingToClaim,
+    #[msg("Withdraw all stake accounts first")]
+    StakeOutstanding,
+    #[msg("Invalid guardian configuration")]
+    InvalidGuardianConfig,
+    #[msg("Signer is not a guardian of this vault")]
+    NotAGuardian,
+    #[msg("A recovery is already in progress")]
+    RecoveryInProgress,
+    #[msg("No recovery is in progress")]
+    NoRecoveryInProgress,
+    #[msg("Recovery needs more guardian approvals or is still in its challenge period")]
+    RecoveryNotReady,
+    #[msg("Inactivity period must be positive")]
+    InvalidInheritanceConfig,
+    #[msg("The owner has been active within the inactivity period")]
+    OwnerStillActive,
+    #[msg("Missing or invalid Ed25519 signature instruction")]
+    InvalidSignatureInstruction,
+    #[msg("Withdrawal authorization has expired")]
+    AuthorizationExpired,
+    #[msg("Nonce has already been used")]
+    NonceAlreadyUsed,
+    #[msg("Invalid session configuration")]
+    InvalidSessionConfig,
+    #[msg("Session has expired")]
+    SessionExpired,
+    #[msg("Session key is not allowed to do this")]
+    SessionNotPermitted,
+    #[msg("Session withdrawal cap exceeded")]
+    SessionCapExceeded,
+    #[msg("Not a vault account, or an unknown vault layout")]
+    InvalidVaultAccount,
+    #[msg("Vault already uses the current layout")]
+    VaultUpToDate,
+    #[msg("Pooled vaults only pay out through pool_withdraw")]
+    VaultPooled,
+    #[msg("Vault is not pooled")]
+    VaultNotPooled,
+    #[msg("Vault must be empty to enable pooling")]
+    VaultNotEmpty,
+    #[msg("Not enough shares")]
+    InsufficientShares,
+    #[msg("Destination is still in its allowlist delay")]
+    DestinationNotActive,
+    #[msg("Invalid governance configuration")]
+    InvalidGovernanceConfig,
+    #[msg("Governance is already active")]
+    GovernanceActive,
+    #[msg("Governance is not active yet")]
+    GovernanceNotActive,
+    #[msg("Voting on this proposal has ended")]
+    VotingClosed,
+    #[msg("Proposal is still in its voting period or execution delay")]
+    ProposalNotReady,
+    #[msg("Proposal did not pass")]
+    ProposalNotPassed,
+    #[msg("Invalid subscription configuration")]
+    InvalidSubscriptionConfig,
+    #[msg("The next subscription period has not elapsed yet")]
+    SubscriptionNotDue,
+    #[msg("Subscription has ended")]
+    SubscriptionEnded,
+    #[msg("Pass the creator's owner index to close a named vault")]
+    OwnerIndexRequired,
+    #[msg("Recipient is not an active allowlisted destination")]
+    DestinationNotAllowed,
+    #[msg("No pending allowlist change")]
+    NoPendingAllowlistChange,
+    #[msg("Allowlist change is still timelocked")]
+    AllowlistChangeTimelocked,
+    #[msg("Turn off the destination allowlist first")]
+    AllowlistEnforced,
+    #[msg("Pass the vault's history account - it is attached to this vault")]
+    HistoryRequired,
+    #[msg("Withdraw the vault's tokens and close its token accounts first")]
+    TokenPositionsOutstanding,
+    #[msg("The vault's token account still holds tokens")]
+    TokenAccountNotEmpty,
+    #[msg("Not available on a tokenized vault - the grant would survive a sale of its ownership token")]
+    VaultTokenized,
+    #[msg("A vault's ownership token cannot be deposited into it")]
+    OwnershipTokenDeposit,
+    #[msg("Pass the ownership token account and token program to tokenize the vault")]
+    OwnershipAccountsRequired,
 }
@@ -0,0 +1,253 @@
+use anchor_lang::prelude::*;
+use crate::state::{Vault, VaultLock, WithdrawalLimit};
+
+// All events are emitted with emit_cpi! so indexers can read them from inner
+// instructions even when program logs are truncated.
+// Every event carries the slot and unix timestamp it was emitted at.
+
+// Emitted when a vault (default or named) is created
+#[event]
+pub struct VaultInitialized {
+    pub vault: Pubkey,
+    pub owner: Pubkey,
+    pub name: String,
+    pub slot: u64,
+    pub unix_timestamp: i64,
+}
+
+// Emitted for every deposit, with the vault totals after it
+#[event]
+pub struct Deposited {
+    pub vault: Pubkey,
+    pub owner: Pubkey,
+    pub depositor: Pubkey,
+    pub amount: u64,
+    pub total_deposits: u64,
+    pub total_withdrawals: u64,
+    pub slot: u64,
+    pub unix_timestamp: i64,
+}
+
+// Emitted for every payout (owner, delegate, session or multisig), with the vault totals after it
+#[event]
+pub struct Withdrawn {
+    pub vault: Pubkey,
+    pub owner: Pubkey,
+    // The key that authorized the withdrawal (owner, delegate, session key or proposal executor)
+    pub authority: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub total_deposits: u64,
+    pub total_withdrawals: u64,
+    pub slot: u64,
+    pub unix_timestamp: i64,
+}
+
+impl Deposited {
+    pub fn new(vault: &Account<Vault>, depositor: Pubkey, amount: u64, clock: &Clock) -> Self {
+        Self {
+            vault: vault.key(),
+            owner: vault.owner,
+            depositor,
+            amount,
+            total_deposits: vault.total_deposits,
+            total_withdrawals: vault.total_withdrawals,
+            slot: clock.slot,
+            unix_timestamp: clock.unix_timestamp,
+        }
+    }
+}
+
+impl Withdrawn {
+    pub fn new(
+        vault: &Account<Vault>,
+        authority: Pubkey,
+        recipient: Pubkey,
+        amount: u64,
+        clock: &Clock,
+    ) -> Self {
+        Self {
+            vault: vault.key(),
+            owner: vault.owner,
+            authority,
+            recipient,
+            amount,
+            total_deposits: vault.total_deposits,
+            total_withdrawals: vault.total_withdrawals,
+            slot: clock.slot,
+            unix_timestamp: clock.unix_timestamp,
+        }
+    }
+}
+
+// Emitted when a vault is closed - carries the final totals so the SOL can be accounted for
+#[event]
+pub struct VaultClosed {
+    pub vault: Pubkey,
+    pub owner: Pubkey,
+    pub recipient: Pubkey,
+    // Whole closing balance, rent-exempt reserve included
+    pub lamports: u64,
+    pub total_deposits: u64,
+    pub total_withdrawals: u64,
+    pub slot: u64,
+    pub unix_timestamp: i64,
+}
+
+// Emitted when a pending owner accepts ownership of a vault or a recovery hands it to a new key
+#[event]
+pub struct OwnerChanged {
+    pub vault: Pubkey,
+    pub previous_owner: Pubkey,
+    pub new_owner: Pubkey,
+    pub slot: u64,
+    pub unix_timestamp: i64,
+}
+
+// Emitted when a lock is set or extended
+#[event]
+pub struct LockChanged {
+    pub vault: Pubkey,
+    pub owner: Pubkey,
+    pub lock: VaultLock,
+    pub slot: u64,
+    pub unix_timestamp: i64,
+}
+
+// Emitted when a withdrawal limit change is applied or queued
+#[event]
+pub struct WithdrawalLimitChanged {
+    pub vault: Pubkey,
+    pub owner: Pubkey,
+    pub limit: Option<WithdrawalLimit>,
+    // When the change takes effect - equal to unix_timestamp if it applied immediately
+    pub effective_at: i64,
+    pub slot: u64,
+    pub unix_timestamp: i64,
+}
+
+// Emitted when a guardian starts a recovery - the owner has RECOVERY_CHALLENGE_SECONDS to veto it
+#[event]
+pub struct RecoveryStarted {
+    pub vault: Pubkey,
+    pub guardian: Pubkey,
+    pub new_owner: Pubkey,
+    pub slot: u64,
+    pub unix_timestamp: i64,
+}
+
+// Emitted when the owner vetoes a recovery
+#[event]
+pub struct RecoveryCancelled {
+    pub vault: Pubkey,
+    pub owner: Pubkey,
+    pub new_owner: Pubkey,
+    pub slot: u64,
+    pub unix_timestamp: i64,
+}
+
+// Emitted when the owner sets, changes or removes the vault's beneficiary
+#[event]
+pub struct BeneficiaryChanged {
+    pub vault: Pubkey,
+    pub owner: Pubkey,
+    pub beneficiary: Option<Pubkey>,
+    pub inactivity_period: i64,
+    pub slot: u64,
+    pub unix_timestamp: i64,
+}
+
+// Emitted when migrate_vault upgrades a vault to a newer layout
+#[event]
+pub struct VaultMigrated {
+    pub vault: Pubkey,
+    pub owner: Pubkey,
+    pub from_version: u8,
+    pub to_version: u8,
+    pub slot: u64,
+    pub unix_timestamp: i64,
+}
+
+// Emitted when the owner allowlists a destination - it can receive withdrawals from active_at
+#[event]
+pub struct DestinationAdded {
+    pub vault: Pubkey,
+    pub owner: Pubkey,
+    pub destination: Pubkey,
+    pub active_at: i64,
+    pub slot: u64,
+    pub unix_timestamp: i64,
+}
+
+// Emitted when the owner removes a destination from the allowlist
+#[event]
+pub struct DestinationRemoved {
+    pub vault: Pubkey,
+    pub owner: Pubkey,
+    pub destination: Pubkey,
+    pub slot: u64,
+    pub unix_timestamp: i64,
+}
+
+// Emitted when the allowlist switch is flipped, or a disable is queued or applied
+#[event]
+pub struct AllowlistChanged {
+    pub vault: Pubkey,
+    pub owner: Pubkey,
+    pub enforced: bool,
+    // When the change takes effect - equal to unix_timestamp if it applied immediately
+    pub effective_at: i64,
+    pub slot: u64,
+    pub unix_timestamp: i64,
+}
+
+// Emitted when a vault's governance is activated and takes over from the owner
+#[event]
+pub struct GovernanceActivated {
+    pub vault: Pubkey,
+    pub governance: Pubkey,
+    pub total_weight: u64,
+    pub slot: u64,
+    pub unix_timestamp: i64,
+}
+
+// Emitted when lamports move directly from one vault to another, with both vaults' totals after it
+#[event]
+pub struct VaultTransferred {
+    pub from_vault: Pubkey,
+    pub to_vault: Pubkey,
+    pub authority: Pubkey,
+    pub amount: u64,
+    pub from_total_withdrawals: u64,
+    pub to_total_deposits: u64,
+    pub slot: u64,
+    pub unix_timestamp: i64,
+}
+
+// Emitted for every SPL / Token-2022 deposit, with the mint's totals after it
+#[event]
+pub struct TokenDeposited {
+    pub vault: Pubkey,
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    pub depositor: Pubkey,
+    pub amount: u64,
+    pub total_deposited: u64,
+    pub total_withdrawn: u64,
+    pub slot: u64,
+    pub unix_timestamp: i64,
+}
+
+// Emitted for every SPL / Token-2022 withdrawal, with the mint's totals after it
+#[event]
+pub struct TokenWithdrawn {
+    pub vault: Pubkey,
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub total_deposited: u64,
+    pub total_withdrawn: u64,
+    pub slot: u64,
+    pub unix_timestamp: i64,
+}
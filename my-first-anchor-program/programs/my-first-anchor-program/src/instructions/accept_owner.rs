@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+use crate::contexts::AcceptOwner;
+use crate::events::OwnerChanged;
+
+pub fn handler(ctx: Context<AcceptOwner>) -> Result<()> {
+    let clock = Clock::get()?;
+    let vault = &mut ctx.accounts.vault;
+    let previous_owner = vault.owner;
+
+    // The PDA is derived from `vault.creator`, so the vault keeps its address and lamports
+    vault.owner = ctx.accounts.signer.key();
+    vault.pending_owner = None;
+    // Taking over counts as owner activity for the dead-man's switch
+    vault.touch(clock.unix_timestamp);
+
+    emit_cpi!(OwnerChanged {
+        vault: ctx.accounts.vault.key(),
+        previous_owner,
+        new_owner: ctx.accounts.vault.owner,
+        slot: clock.slot,
+        unix_timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
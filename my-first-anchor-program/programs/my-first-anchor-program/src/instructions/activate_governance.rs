@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+use crate::contexts::ActivateGovernance;
+use crate::errors::VaultError;
+use crate::events::GovernanceActivated;
+
+pub fn handler(ctx: Context<ActivateGovernance>) -> Result<()> {
+    let governance = &mut ctx.accounts.governance;
+
+    require!(!governance.active, VaultError::GovernanceActive);
+    // Otherwise no proposal could ever pass
+    require!(
+        governance.total_weight >= governance.quorum_weight,
+        VaultError::InvalidGovernanceConfig
+    );
+
+    governance.active = true;
+
+    // Same switch as create_multisig: owner-only paths are disabled from now on
+    ctx.accounts.vault.multisig = true;
+    ctx.accounts.vault.pending_owner = None;
+
+    let clock = Clock::get()?;
+    emit_cpi!(GovernanceActivated {
+        vault: ctx.accounts.vault.key(),
+        governance: ctx.accounts.governance.key(),
+        total_weight: ctx.accounts.governance.total_weight,
+        slot: clock.slot,
+        unix_timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+use crate::contexts::AddDelegate;
+use crate::errors::VaultError;
+
+pub fn handler(
+    ctx: Context<AddDelegate>,
+    delegate: Pubkey,
+    destination: Option<Pubkey>,
+    lifetime_cap: u64,
+    period_cap: u64,
+    period_seconds: i64,
+    expires_at: i64,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+
+    require!(period_seconds > 0, VaultError::InvalidDelegateConfig);
+    require!(expires_at > now, VaultError::InvalidDelegateConfig);
+
+    let vault_delegate = &mut ctx.accounts.vault_delegate;
+    vault_delegate.vault = ctx.accounts.vault.key();
+    vault_delegate.delegate = delegate;
+    vault_delegate.destination = destination;
+    vault_delegate.lifetime_cap = lifetime_cap;
+    vault_delegate.total_spent = 0;
+    vault_delegate.period_cap = period_cap;
+    vault_delegate.period_seconds = period_seconds;
+    vault_delegate.period_start = now;
+    vault_delegate.period_spent = 0;
+    vault_delegate.expires_at = expires_at;
+
+    ctx.accounts.vault.grant_count = ctx.accounts.vault.grant_count
+        .checked_add(1)
+        .ok_or(VaultError::InvalidAmount)?;
+
+    Ok(())
+}
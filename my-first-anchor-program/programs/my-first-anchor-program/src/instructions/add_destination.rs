@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+use crate::contexts::AddDestination;
+use crate::errors::VaultError;
+use crate::events::DestinationAdded;
+use crate::state::DESTINATION_DELAY_SECONDS;
+
+pub fn handler(ctx: Context<AddDestination>, destination: Pubkey) -> Result<()> {
+    require!(
+        destination != ctx.accounts.vault.key(),
+        VaultError::InvalidDestination
+    );
+
+    // Adding widens where funds can go, so it waits out the delay like loosening a limit
+    let clock = Clock::get()?;
+    let active_at = clock.unix_timestamp + DESTINATION_DELAY_SECONDS;
+
+    let allowed_destination = &mut ctx.accounts.allowed_destination;
+    allowed_destination.vault = ctx.accounts.vault.key();
+    allowed_destination.destination = destination;
+    allowed_destination.active_at = active_at;
+
+    // Counted like a grant so close_vault cannot leave the entry behind
+    ctx.accounts.vault.grant_count = ctx.accounts.vault.grant_count
+        .checked_add(1)
+        .ok_or(VaultError::InvalidAmount)?;
+
+    emit_cpi!(DestinationAdded {
+        vault: ctx.accounts.vault.key(),
+        owner: ctx.accounts.vault.owner,
+        destination,
+        active_at,
+        slot: clock.slot,
+        unix_timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,20 @@
+use anchor_lang::prelude::*;
+use crate::contexts::AddGovernanceMember;
+use crate::errors::VaultError;
+
+pub fn handler(ctx: Context<AddGovernanceMember>, member: Pubkey, weight: u64) -> Result<()> {
+    // Weights are fixed once voting starts, so no proposal is decided by a moving target
+    require!(!ctx.accounts.governance.active, VaultError::GovernanceActive);
+    require!(weight > 0, VaultError::InvalidGovernanceConfig);
+
+    let governance_member = &mut ctx.accounts.governance_member;
+    governance_member.governance = ctx.accounts.governance.key();
+    governance_member.member = member;
+    governance_member.weight = weight;
+
+    ctx.accounts.governance.total_weight = ctx.accounts.governance.total_weight
+        .checked_add(weight)
+        .ok_or(VaultError::InvalidAmount)?;
+
+    Ok(())
+}
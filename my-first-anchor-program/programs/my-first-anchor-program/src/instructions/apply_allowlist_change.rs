@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+use crate::contexts::AllowlistAction;
+use crate::errors::VaultError;
+use crate::events::AllowlistChanged;
+
+pub fn handler(ctx: Context<AllowlistAction>) -> Result<()> {
+    let vault = &mut ctx.accounts.vault;
+
+    let disable_at = vault.allowlist_disable_at
+        .ok_or(VaultError::NoPendingAllowlistChange)?;
+
+    let clock = Clock::get()?;
+    require!(
+        clock.unix_timestamp >= disable_at,
+        VaultError::AllowlistChangeTimelocked
+    );
+
+    vault.allowlist_enforced = false;
+    vault.allowlist_disable_at = None;
+
+    emit_cpi!(AllowlistChanged {
+        vault: ctx.accounts.vault.key(),
+        owner: ctx.accounts.vault.owner,
+        enforced: false,
+        effective_at: clock.unix_timestamp,
+        slot: clock.slot,
+        unix_timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+use crate::contexts::WithdrawalLimitAction;
+use crate::errors::VaultError;
+use crate::events::WithdrawalLimitChanged;
+
+pub fn handler(ctx: Context<WithdrawalLimitAction>) -> Result<()> {
+    let vault = &mut ctx.accounts.vault;
+
+    let pending = vault.pending_limit_change
+        .ok_or(VaultError::NoPendingLimitChange)?;
+
+    let clock = Clock::get()?;
+    require!(
+        clock.unix_timestamp >= pending.effective_at,
+        VaultError::LimitChangeTimelocked
+    );
+
+    vault.withdrawal_limit = pending.limit;
+    vault.pending_limit_change = None;
+
+    emit_cpi!(WithdrawalLimitChanged {
+        vault: ctx.accounts.vault.key(),
+        owner: ctx.accounts.vault.owner,
+        limit: pending.limit,
+        effective_at: clock.unix_timestamp,
+        slot: clock.slot,
+        unix_timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,24 @@
+use anchor_lang::prelude::*;
+use crate::contexts::ApproveProposal;
+use crate::errors::VaultError;
+
+pub fn handler(ctx: Context<ApproveProposal>) -> Result<()> {
+    let signer = ctx.accounts.signer.key();
+
+    require!(
+        ctx.accounts.multisig.is_member(&signer),
+        VaultError::NotAMultisigMember
+    );
+    require!(
+        !ctx.accounts.proposal.executed,
+        VaultError::ProposalAlreadyExecuted
+    );
+    require!(
+        !ctx.accounts.proposal.approvals.contains(&signer),
+        VaultError::AlreadyApproved
+    );
+
+    ctx.accounts.proposal.approvals.push(signer);
+
+    Ok(())
+}
@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+use crate::contexts::GuardianAction;
+use crate::errors::VaultError;
+
+pub fn handler(ctx: Context<GuardianAction>) -> Result<()> {
+    let signer = ctx.accounts.signer.key();
+    let guardian_set = &mut ctx.accounts.guardian_set;
+
+    require!(guardian_set.is_guardian(&signer), VaultError::NotAGuardian);
+    let recovery = guardian_set.recovery
+        .as_mut()
+        .ok_or(VaultError::NoRecoveryInProgress)?;
+    require!(
+        !recovery.approvals.contains(&signer),
+        VaultError::AlreadyApproved
+    );
+
+    recovery.approvals.push(signer);
+
+    Ok(())
+}
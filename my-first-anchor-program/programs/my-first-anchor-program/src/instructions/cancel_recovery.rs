@@ -0,0 +1,22 @@
+use anchor_lang::prelude::*;
+use crate::contexts::CancelRecovery;
+use crate::errors::VaultError;
+use crate::events::RecoveryCancelled;
+
+pub fn handler(ctx: Context<CancelRecovery>) -> Result<()> {
+    // The owner still holds their key, so the recovery was not wanted
+    let recovery = ctx.accounts.guardian_set.recovery
+        .take()
+        .ok_or(VaultError::NoRecoveryInProgress)?;
+
+    let clock = Clock::get()?;
+    emit_cpi!(RecoveryCancelled {
+        vault: ctx.accounts.vault.key(),
+        owner: ctx.accounts.vault.owner,
+        new_owner: recovery.new_owner,
+        slot: clock.slot,
+        unix_timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
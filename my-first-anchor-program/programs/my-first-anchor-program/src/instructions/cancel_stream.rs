@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+use crate::contexts::CancelStream;
+use crate::errors::VaultError;
+use crate::events::Withdrawn;
+use crate::state::{HistoryKind, VaultHistory};
+use crate::instructions::withdraw::pay_out;
+
+pub fn handler(ctx: Context<CancelStream>) -> Result<()> {
+    let clock = Clock::get()?;
+
+    // Settle what has vested so far - the unvested rest simply stays in the vault
+    let amount = ctx.accounts.stream.claimable(clock.unix_timestamp)?;
+    if amount > 0 {
+        let recipient_info = ctx.accounts.recipient.to_account_info();
+        pay_out(
+            &mut ctx.accounts.vault,
+            &recipient_info,
+            amount,
+            ctx.accounts.allowed_destination.as_ref(),
+        )?;
+
+        VaultHistory::record(
+            &ctx.accounts.history,
+            HistoryKind::Withdrawal,
+            amount,
+            recipient_info.key(),
+            &ctx.accounts.vault.to_account_info(),
+        )?;
+
+        emit_cpi!(Withdrawn::new(
+            &ctx.accounts.vault,
+            ctx.accounts.signer.key(),
+            recipient_info.key(),
+            amount,
+            &clock,
+        ));
+    }
+
+    // The stream PDA itself is closed by the `close = signer` constraint
+    ctx.accounts.vault.grant_count = ctx.accounts.vault.grant_count
+        .checked_sub(1)
+        .ok_or(VaultError::InvalidAmount)?;
+
+    Ok(())
+}
@@ -0,0 +1,13 @@
+use anchor_lang::prelude::*;
+use crate::contexts::CancelSubscription;
+use crate::errors::VaultError;
+
+pub fn handler(ctx: Context<CancelSubscription>) -> Result<()> {
+    // The subscription PDA is closed by the `close = signer` constraint, which also
+    // refunds its rent to the owner. Periods the merchant has not collected are forfeited.
+    ctx.accounts.vault.grant_count = ctx.accounts.vault.grant_count
+        .checked_sub(1)
+        .ok_or(VaultError::InvalidAmount)?;
+
+    Ok(())
+}
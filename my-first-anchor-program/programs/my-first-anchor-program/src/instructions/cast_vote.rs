@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+use crate::contexts::CastVote;
+use crate::errors::VaultError;
+
+pub fn handler(ctx: Context<CastVote>, approve: bool) -> Result<()> {
+    require!(
+        Clock::get()?.unix_timestamp < ctx.accounts.proposal.voting_ends_at,
+        VaultError::VotingClosed
+    );
+
+    // A second vote fails on the `init` of the vote record
+    let weight = ctx.accounts.governance_member.weight;
+    let vote_record = &mut ctx.accounts.vote_record;
+    vote_record.proposal = ctx.accounts.proposal.key();
+    vote_record.member = ctx.accounts.signer.key();
+    vote_record.approve = approve;
+    vote_record.weight = weight;
+
+    let proposal = &mut ctx.accounts.proposal;
+    if approve {
+        proposal.yes_weight = proposal.yes_weight
+            .checked_add(weight)
+            .ok_or(VaultError::InvalidAmount)?;
+    } else {
+        proposal.no_weight = proposal.no_weight
+            .checked_add(weight)
+            .ok_or(VaultError::InvalidAmount)?;
+    }
+
+    Ok(())
+}
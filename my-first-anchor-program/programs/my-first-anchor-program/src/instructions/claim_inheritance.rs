@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+use crate::contexts::ClaimInheritance;
+use crate::events::OwnerChanged;
+use crate::errors::VaultError;
+
+pub fn handler(ctx: Context<ClaimInheritance>) -> Result<()> {
+    let clock = Clock::get()?;
+    require!(
+        ctx.accounts.vault.is_inheritable(clock.unix_timestamp),
+        VaultError::OwnerStillActive
+    );
+
+    // The beneficiary takes over the vault instead of draining it: delegates, streams,
+    // guardians and stake left behind by the old owner can only be cleaned up by an owner,
+    // and the lock, limit and allowlist keep protecting the funds under the new one
+    let vault = &mut ctx.accounts.vault;
+    let previous_owner = vault.owner;
+    vault.owner = ctx.accounts.signer.key();
+    vault.pending_owner = None;
+    // The switch has fired - the new owner sets up their own beneficiary if they want one
+    vault.beneficiary = None;
+    vault.inactivity_period = 0;
+    vault.touch(clock.unix_timestamp);
+
+    emit_cpi!(OwnerChanged {
+        vault: ctx.accounts.vault.key(),
+        previous_owner,
+        new_owner: ctx.accounts.vault.owner,
+        slot: clock.slot,
+        unix_timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+use crate::contexts::ClaimStream;
+use crate::errors::VaultError;
+use crate::events::Withdrawn;
+use crate::state::{HistoryKind, VaultHistory};
+use crate::instructions::withdraw::pay_out;
+
+pub fn handler(ctx: Context<ClaimStream>) -> Result<()> {
+    let clock = Clock::get()?;
+
+    let amount = ctx.accounts.stream.claimable(clock.unix_timestamp)?;
+    require!(amount > 0, VaultError::NothingToClaim);
+
+    ctx.accounts.stream.claimed = ctx.accounts.stream.claimed
+        .checked_add(amount)
+        .ok_or(VaultError::InvalidAmount)?;
+
+    // Claims go through the same lock, limit and rent checks as any withdrawal
+    let signer_info = ctx.accounts.signer.to_account_info();
+    pay_out(
+        &mut ctx.accounts.vault,
+        &signer_info,
+        amount,
+        ctx.accounts.allowed_destination.as_ref(),
+    )?;
+
+    VaultHistory::record(
+        &ctx.accounts.history,
+        HistoryKind::Withdrawal,
+        amount,
+        signer_info.key(),
+        &ctx.accounts.vault.to_account_info(),
+    )?;
+
+    emit_cpi!(Withdrawn::new(
+        &ctx.accounts.vault,
+        signer_info.key(),
+        signer_info.key(),
+        amount,
+        &clock,
+    ));
+
+    Ok(())
+}
@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{close_account, CloseAccount};
+use crate::contexts::CloseTokenAccount;
+use crate::errors::VaultError;
+
+pub fn handler(ctx: Context<CloseTokenAccount>) -> Result<()> {
+    // The vault PDA owns the token account, so closing it is signed with the vault seeds
+    let vault = &ctx.accounts.vault;
+    let vault_bump = [ctx.bumps.vault];
+    let vault_seeds: &[&[u8]] = &[b"vault", vault.creator.as_ref(), vault.name.as_bytes(), &vault_bump];
+
+    close_account(CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        CloseAccount {
+            account: ctx.accounts.vault_token.to_account_info(),
+            destination: ctx.accounts.signer.to_account_info(),
+            authority: ctx.accounts.vault.to_account_info(),
+        },
+        &[vault_seeds],
+    ))?;
+
+    // The totals PDA is closed by the `close = signer` constraint
+    ctx.accounts.vault.token_positions = ctx.accounts.vault.token_positions
+        .checked_sub(1)
+        .ok_or(VaultError::InvalidAmount)?;
+
+    Ok(())
+}
@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+use crate::contexts::CloseVault;
+use crate::events::VaultClosed;
+use crate::errors::VaultError;
+use crate::instructions::withdraw::check_destination;
+
+pub fn handler(ctx: Context<CloseVault>) -> Result<()> {
+    let clock = Clock::get()?;
+    let lamports = ctx.accounts.vault.to_account_info().lamports();
+
+    // Closing drains the vault, so it must respect the lock like any withdrawal
+    require!(
+        !ctx.accounts.vault.lock.is_active(&clock),
+        VaultError::VaultLocked
+    );
+    require!(
+        ctx.accounts.vault.grant_count == 0,
+        VaultError::GrantsOutstanding
+    );
+    require!(
+        ctx.accounts.vault.staked_lamports == 0,
+        VaultError::StakeOutstanding
+    );
+    require!(
+        ctx.accounts.vault.token_positions == 0,
+        VaultError::TokenPositionsOutstanding
+    );
+    require!(!ctx.accounts.vault.pooled, VaultError::VaultPooled);
+    check_destination(
+        &ctx.accounts.vault,
+        &ctx.accounts.recipient.key(),
+        ctx.accounts.allowed_destination.as_ref(),
+        clock.unix_timestamp,
+    )?;
+
+    // The whole balance counts against the withdrawal limit, otherwise
+    // closing would be a way around it
+    ctx.accounts.vault.consume_withdrawal_allowance(lamports, clock.unix_timestamp)?;
+
+    // Drop the vault from its creator's index, if the caller passed it
+    let vault_key = ctx.accounts.vault.key();
+    if let Some(owner_index) = ctx.accounts.owner_index.as_mut() {
+        owner_index.vaults.retain(|key| *key != vault_key);
+    }
+
+    let vault = &ctx.accounts.vault;
+
+    // The `close = recipient` constraint moves the lamports after the handler returns,
+    // so record the final state now
+    emit_cpi!(VaultClosed {
+        vault: vault.key(),
+        owner: vault.owner,
+        recipient: ctx.accounts.recipient.key(),
+        lamports,
+        total_deposits: vault.total_deposits,
+        total_withdrawals: vault.total_withdrawals,
+        slot: clock.slot,
+        unix_timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+use crate::contexts::CollectSubscription;
+use crate::errors::VaultError;
+use crate::events::Withdrawn;
+use crate::state::{HistoryKind, VaultHistory};
+use crate::instructions::withdraw::pay_out;
+
+pub fn handler(ctx: Context<CollectSubscription>) -> Result<()> {
+    let clock = Clock::get()?;
+    let subscription = &mut ctx.accounts.subscription;
+
+    // One elapsed period per call - a merchant that fell behind catches up one period
+    // at a time, but can never collect a period before it is over
+    require!(clock.unix_timestamp >= subscription.next_collection_at, VaultError::SubscriptionNotDue);
+    if let Some(ends_at) = subscription.ends_at {
+        require!(subscription.next_collection_at <= ends_at, VaultError::SubscriptionEnded);
+    }
+
+    subscription.next_collection_at = subscription.next_collection_at
+        .checked_add(subscription.period_seconds)
+        .ok_or(VaultError::InvalidAmount)?;
+    let amount = subscription.amount_per_period;
+
+    // Collections go through the same lock, limit and rent checks as any withdrawal
+    let signer_info = ctx.accounts.signer.to_account_info();
+    pay_out(
+        &mut ctx.accounts.vault,
+        &signer_info,
+        amount,
+        ctx.accounts.allowed_destination.as_ref(),
+    )?;
+
+    VaultHistory::record(
+        &ctx.accounts.history,
+        HistoryKind::Withdrawal,
+        amount,
+        signer_info.key(),
+        &ctx.accounts.vault.to_account_info(),
+    )?;
+
+    emit_cpi!(Withdrawn::new(
+        &ctx.accounts.vault,
+        signer_info.key(),
+        signer_info.key(),
+        amount,
+        &clock,
+    ));
+
+    Ok(())
+}
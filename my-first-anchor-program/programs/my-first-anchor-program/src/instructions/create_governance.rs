@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+use crate::contexts::CreateGovernance;
+use crate::errors::VaultError;
+
+pub fn handler(
+    ctx: Context<CreateGovernance>,
+    quorum_weight: u64,
+    voting_period: i64,
+    execution_delay: i64,
+) -> Result<()> {
+    require!(quorum_weight > 0, VaultError::InvalidGovernanceConfig);
+    require!(voting_period > 0, VaultError::InvalidGovernanceConfig);
+    require!(execution_delay >= 0, VaultError::InvalidGovernanceConfig);
+
+    let governance = &mut ctx.accounts.governance;
+    governance.vault = ctx.accounts.vault.key();
+    governance.quorum_weight = quorum_weight;
+    governance.voting_period = voting_period;
+    governance.execution_delay = execution_delay;
+    governance.total_weight = 0;
+    governance.active = false;
+    governance.proposal_count = 0;
+
+    Ok(())
+}
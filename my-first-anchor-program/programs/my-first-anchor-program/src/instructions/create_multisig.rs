@@ -0,0 +1,18 @@
+use anchor_lang::prelude::*;
+use crate::contexts::CreateMultisig;
+
+pub fn handler(ctx: Context<CreateMultisig>, members: Vec<Pubkey>, threshold: u8) -> Result<()> {
+    let multisig = &mut ctx.accounts.multisig;
+    multisig.vault = ctx.accounts.vault.key();
+    multisig.members = members;
+    multisig.threshold = threshold;
+    multisig.proposal_count = 0;
+    multisig.validate()?;
+
+    // From now on withdrawals and config changes go through proposals
+    ctx.accounts.vault.multisig = true;
+    // A proposed owner must not be able to take the vault back from the members
+    ctx.accounts.vault.pending_owner = None;
+
+    Ok(())
+}
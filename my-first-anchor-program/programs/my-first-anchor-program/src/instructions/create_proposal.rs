@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+use crate::contexts::CreateProposal;
+use crate::state::MultisigAction;
+use crate::errors::VaultError;
+
+pub fn handler(ctx: Context<CreateProposal>, action: MultisigAction) -> Result<()> {
+    let signer = ctx.accounts.signer.key();
+
+    require!(
+        ctx.accounts.multisig.is_member(&signer),
+        VaultError::NotAMultisigMember
+    );
+
+    let proposal = &mut ctx.accounts.proposal;
+    proposal.multisig = ctx.accounts.multisig.key();
+    proposal.index = ctx.accounts.multisig.proposal_count;
+    proposal.proposer = signer;
+    proposal.action = action;
+    // Creating a proposal counts as the proposer's approval
+    proposal.approvals = vec![signer];
+    proposal.executed = false;
+
+    ctx.accounts.multisig.proposal_count = ctx.accounts.multisig.proposal_count
+        .checked_add(1)
+        .ok_or(VaultError::InvalidAmount)?;
+
+    Ok(())
+}
@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+use crate::contexts::CreateSession;
+use crate::errors::VaultError;
+use crate::state::SessionScope;
+
+pub fn handler(
+    ctx: Context<CreateSession>,
+    session_key: Pubkey,
+    scope: SessionScope,
+    expires_at: i64,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+
+    require!(expires_at > now, VaultError::InvalidSessionConfig);
+    if let SessionScope::Withdraw { destination, cap } = scope {
+        require!(cap > 0, VaultError::InvalidSessionConfig);
+        require!(
+            destination != ctx.accounts.vault.key(),
+            VaultError::InvalidDestination
+        );
+    }
+
+    let vault_session = &mut ctx.accounts.vault_session;
+    vault_session.vault = ctx.accounts.vault.key();
+    vault_session.session_key = session_key;
+    vault_session.scope = scope;
+    vault_session.withdrawn = 0;
+    vault_session.expires_at = expires_at;
+
+    ctx.accounts.vault.grant_count = ctx.accounts.vault.grant_count
+        .checked_add(1)
+        .ok_or(VaultError::InvalidAmount)?;
+
+    Ok(())
+}
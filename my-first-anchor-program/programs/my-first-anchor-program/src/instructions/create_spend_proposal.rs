@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+use crate::contexts::CreateSpendProposal;
+use crate::errors::VaultError;
+use crate::state::MAX_MEMO_LEN;
+
+pub fn handler(
+    ctx: Context<CreateSpendProposal>,
+    recipient: Pubkey,
+    amount: u64,
+    memo: String,
+) -> Result<()> {
+    require!(ctx.accounts.governance.active, VaultError::GovernanceNotActive);
+    require!(amount > 0, VaultError::InvalidAmount);
+    require!(memo.len() <= MAX_MEMO_LEN, VaultError::InvalidGovernanceConfig);
+    require!(
+        recipient != ctx.accounts.governance.vault,
+        VaultError::InvalidDestination
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+    let governance = &ctx.accounts.governance;
+
+    let proposal = &mut ctx.accounts.proposal;
+    proposal.governance = governance.key();
+    proposal.index = governance.proposal_count;
+    proposal.proposer = ctx.accounts.signer.key();
+    proposal.recipient = recipient;
+    proposal.amount = amount;
+    proposal.memo = memo;
+    proposal.voting_ends_at = now
+        .checked_add(governance.voting_period)
+        .ok_or(VaultError::InvalidAmount)?;
+    proposal.yes_weight = 0;
+    proposal.no_weight = 0;
+    proposal.executed = false;
+
+    ctx.accounts.governance.proposal_count = ctx.accounts.governance.proposal_count
+        .checked_add(1)
+        .ok_or(VaultError::InvalidAmount)?;
+
+    Ok(())
+}
@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+use crate::contexts::CreateStream;
+use crate::errors::VaultError;
+
+pub fn handler(
+    ctx: Context<CreateStream>,
+    rate_per_second: u64,
+    start: i64,
+    end: i64,
+    cliff: Option<i64>,
+) -> Result<()> {
+    require!(rate_per_second > 0, VaultError::InvalidStreamConfig);
+    require!(end > start, VaultError::InvalidStreamConfig);
+    if let Some(cliff) = cliff {
+        require!(cliff >= start && cliff <= end, VaultError::InvalidStreamConfig);
+    }
+
+    // The full stream amount must be representable
+    rate_per_second
+        .checked_mul((end - start) as u64)
+        .ok_or(VaultError::InvalidStreamConfig)?;
+
+    let stream = &mut ctx.accounts.stream;
+    stream.vault = ctx.accounts.vault.key();
+    stream.recipient = ctx.accounts.recipient.key();
+    stream.rate_per_second = rate_per_second;
+    stream.start = start;
+    stream.end = end;
+    stream.cliff = cliff;
+    stream.claimed = 0;
+
+    ctx.accounts.vault.grant_count = ctx.accounts.vault.grant_count
+        .checked_add(1)
+        .ok_or(VaultError::InvalidAmount)?;
+
+    Ok(())
+}
@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+use crate::contexts::CreateSubscription;
+use crate::errors::VaultError;
+
+pub fn handler(
+    ctx: Context<CreateSubscription>,
+    amount_per_period: u64,
+    period_seconds: i64,
+    ends_at: Option<i64>,
+) -> Result<()> {
+    require!(amount_per_period > 0, VaultError::InvalidSubscriptionConfig);
+    require!(period_seconds > 0, VaultError::InvalidSubscriptionConfig);
+
+    // The first period starts now and can be collected once it has elapsed
+    let now = Clock::get()?.unix_timestamp;
+    let first_collection_at = now
+        .checked_add(period_seconds)
+        .ok_or(VaultError::InvalidSubscriptionConfig)?;
+    // The subscription must cover at least one whole period
+    if let Some(ends_at) = ends_at {
+        require!(ends_at >= first_collection_at, VaultError::InvalidSubscriptionConfig);
+    }
+
+    let subscription = &mut ctx.accounts.subscription;
+    subscription.vault = ctx.accounts.vault.key();
+    subscription.merchant = ctx.accounts.merchant.key();
+    subscription.amount_per_period = amount_per_period;
+    subscription.period_seconds = period_seconds;
+    subscription.next_collection_at = first_collection_at;
+    subscription.ends_at = ends_at;
+
+    ctx.accounts.vault.grant_count = ctx.accounts.vault.grant_count
+        .checked_add(1)
+        .ok_or(VaultError::InvalidAmount)?;
+
+    Ok(())
+}
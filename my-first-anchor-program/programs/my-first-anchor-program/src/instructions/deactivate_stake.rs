@@ -0,0 +1,23 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::invoke_signed;
+use solana_stake_interface::instruction as stake_instruction;
+use crate::contexts::DeactivateStake;
+
+pub fn handler(ctx: Context<DeactivateStake>) -> Result<()> {
+    let vault = &ctx.accounts.vault;
+    let vault_bump = [ctx.bumps.vault];
+    let vault_seeds: &[&[u8]] = &[b"vault", vault.creator.as_ref(), vault.name.as_bytes(), &vault_bump];
+
+    // The vault PDA is the staker, so it signs the deactivation
+    invoke_signed(
+        &stake_instruction::deactivate_stake(&ctx.accounts.stake_account.key(), &vault.key()),
+        &[
+            ctx.accounts.stake_account.to_account_info(),
+            ctx.accounts.clock.to_account_info(),
+            vault.to_account_info(),
+        ],
+        &[vault_seeds],
+    )?;
+
+    Ok(())
+}
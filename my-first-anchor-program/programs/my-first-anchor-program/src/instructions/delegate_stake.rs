@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::{invoke, invoke_signed};
+use anchor_lang::system_program::{create_account, CreateAccount};
+use solana_stake_interface::instruction as stake_instruction;
+use solana_stake_interface::state::{Authorized, Lockup, StakeStateV2};
+use crate::contexts::DelegateStake;
+use crate::errors::VaultError;
+use crate::instructions::withdraw::move_lamports;
+
+pub fn handler(ctx: Context<DelegateStake>, stake_id: u64, amount: u64) -> Result<()> {
+    require!(amount > 0, VaultError::InvalidAmount);
+
+    let vault_key = ctx.accounts.vault.key();
+    let stake_key = ctx.accounts.stake_account.key();
+    let stake_info = ctx.accounts.stake_account.to_account_info();
+
+    // 1. Create the stake account at its PDA - the owner pays the rent-exempt reserve
+    let stake_id_bytes = stake_id.to_le_bytes();
+    let stake_bump = [ctx.bumps.stake_account];
+    let stake_seeds: &[&[u8]] = &[b"stake", vault_key.as_ref(), &stake_id_bytes, &stake_bump];
+
+    let space = StakeStateV2::size_of();
+    create_account(
+        CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            CreateAccount {
+                from: ctx.accounts.signer.to_account_info(),
+                to: stake_info.clone(),
+            },
+            &[stake_seeds],
+        ),
+        Rent::get()?.minimum_balance(space),
+        space as u64,
+        &solana_stake_interface::program::ID,
+    )?;
+
+    // 2. Fund it from the vault - the lamports stay in program custody,
+    // so this is not recorded as a withdrawal and ignores the lock and limit
+    move_lamports(&ctx.accounts.vault, &stake_info, amount)?;
+
+    // 3. The vault PDA becomes both staker and withdrawer
+    invoke(
+        &stake_instruction::initialize(
+            &stake_key,
+            &Authorized { staker: vault_key, withdrawer: vault_key },
+            &Lockup::default(),
+        ),
+        &[stake_info.clone(), ctx.accounts.rent.to_account_info()],
+    )?;
+
+    // 4. Delegate, signed with the vault seeds
+    let vault = &ctx.accounts.vault;
+    let vault_bump = [ctx.bumps.vault];
+    let vault_seeds: &[&[u8]] = &[b"vault", vault.creator.as_ref(), vault.name.as_bytes(), &vault_bump];
+
+    invoke_signed(
+        &stake_instruction::delegate_stake(&stake_key, &vault_key, &ctx.accounts.vote_account.key()),
+        &[
+            stake_info,
+            ctx.accounts.vote_account.to_account_info(),
+            ctx.accounts.clock.to_account_info(),
+            ctx.accounts.stake_history.to_account_info(),
+            ctx.accounts.stake_config.to_account_info(),
+            vault.to_account_info(),
+        ],
+        &[vault_seeds],
+    )?;
+
+    let vault_stake = &mut ctx.accounts.vault_stake;
+    vault_stake.vault = vault_key;
+    vault_stake.stake_account = stake_key;
+    vault_stake.stake_id = stake_id;
+    vault_stake.vote_account = ctx.accounts.vote_account.key();
+    vault_stake.lamports = amount;
+
+    ctx.accounts.vault.staked_lamports = ctx.accounts.vault.staked_lamports
+        .checked_add(amount)
+        .ok_or(VaultError::InvalidAmount)?;
+
+    Ok(())
+}
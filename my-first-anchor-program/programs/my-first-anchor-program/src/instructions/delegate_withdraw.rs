@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+use crate::contexts::DelegateWithdraw;
+use crate::errors::VaultError;
+use crate::instructions::withdraw::pay_out;
+use crate::events::Withdrawn;
+use crate::state::{HistoryKind, VaultHistory};
+
+pub fn handler(ctx: Context<DelegateWithdraw>, amount: u64) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+
+    require!(
+        now < ctx.accounts.vault_delegate.expires_at,
+        VaultError::DelegateExpired
+    );
+
+    // Counts against both the per-period and the lifetime cap
+    ctx.accounts.vault_delegate.record_spend(amount, now)?;
+
+    let destination_info = ctx.accounts.destination.to_account_info();
+    pay_out(
+        &mut ctx.accounts.vault,
+        &destination_info,
+        amount,
+        ctx.accounts.allowed_destination.as_ref(),
+    )?;
+
+    VaultHistory::record(
+        &ctx.accounts.history,
+        HistoryKind::Withdrawal,
+        amount,
+        destination_info.key(),
+        &ctx.accounts.vault.to_account_info(),
+    )?;
+
+    emit_cpi!(Withdrawn::new(
+        &ctx.accounts.vault,
+        ctx.accounts.signer.key(),
+        destination_info.key(),
+        amount,
+        &Clock::get()?,
+    ));
+
+    Ok(())
+}
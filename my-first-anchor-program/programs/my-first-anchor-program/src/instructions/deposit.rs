@@ -2,6 +2,8 @@ use anchor_lang::prelude::*;
 use anchor_lang::system_program::{transfer, Transfer};
 use crate::contexts::VaultAction;
 use crate::errors::VaultError;
+use crate::events::Deposited;
+use crate::state::{HistoryKind, VaultHistory};
 
 pub fn handler(ctx: Context<VaultAction>, amount: u64) -> Result<()> {
     // Transfer lamports from signer to vault using Anchor CPI
@@ -20,5 +22,24 @@ pub fn handler(ctx: Context<VaultAction>, amount: u64) -> Result<()> {
         .checked_add(amount)
         .ok_or(VaultError::InvalidAmount)?;
     
+    // Owner activity resets the dead-man's switch
+    let clock = Clock::get()?;
+    ctx.accounts.vault.touch(clock.unix_timestamp);
+    
+    VaultHistory::record(
+        &ctx.accounts.history,
+        HistoryKind::Deposit,
+        amount,
+        ctx.accounts.signer.key(),
+        &ctx.accounts.vault.to_account_info(),
+    )?;
+    
+    emit_cpi!(Deposited::new(
+        &ctx.accounts.vault,
+        ctx.accounts.signer.key(),
+        amount,
+        &clock,
+    ));
+    
     Ok(())
 }
@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use crate::contexts::DepositTo;
+use crate::errors::VaultError;
+use crate::events::Deposited;
+use crate::state::{HistoryKind, VaultHistory};
+
+pub fn handler(ctx: Context<DepositTo>, amount: u64) -> Result<()> {
+    require!(amount > 0, VaultError::InvalidAmount);
+
+    // Transfer lamports from the payer to someone else's vault
+    let cpi_context = CpiContext::new(
+        ctx.accounts.system_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.signer.to_account_info(),
+            to: ctx.accounts.vault.to_account_info(),
+        },
+    );
+
+    transfer(cpi_context, amount)?;
+
+    ctx.accounts.vault.total_deposits = ctx.accounts.vault.total_deposits
+        .checked_add(amount)
+        .ok_or(VaultError::InvalidAmount)?;
+
+    // Track this depositor's running total (the receipt is created on the first deposit)
+    let clock = Clock::get()?;
+    let receipt = &mut ctx.accounts.depositor_receipt;
+    receipt.vault = ctx.accounts.vault.key();
+    receipt.depositor = ctx.accounts.signer.key();
+    receipt.total_deposited = receipt.total_deposited
+        .checked_add(amount)
+        .ok_or(VaultError::InvalidAmount)?;
+    receipt.deposit_count = receipt.deposit_count
+        .checked_add(1)
+        .ok_or(VaultError::InvalidAmount)?;
+    receipt.last_deposit_at = clock.unix_timestamp;
+
+    VaultHistory::record(
+        &ctx.accounts.history,
+        HistoryKind::Deposit,
+        amount,
+        ctx.accounts.signer.key(),
+        &ctx.accounts.vault.to_account_info(),
+    )?;
+
+    emit_cpi!(Deposited::new(
+        &ctx.accounts.vault,
+        ctx.accounts.signer.key(),
+        amount,
+        &clock,
+    ));
+
+    Ok(())
+}
@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{transfer_checked, TransferChecked};
+use crate::contexts::DepositToken;
+use crate::errors::VaultError;
+use crate::events::TokenDeposited;
+
+pub fn handler(ctx: Context<DepositToken>, amount: u64) -> Result<()> {
+    require!(amount > 0, VaultError::InvalidAmount);
+
+    let balance_before = ctx.accounts.vault_token.amount;
+    transfer_checked(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.signer_token.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                to: ctx.accounts.vault_token.to_account_info(),
+                authority: ctx.accounts.signer.to_account_info(),
+            },
+        ),
+        amount,
+        ctx.accounts.mint.decimals,
+    )?;
+
+    // Token-2022 transfer fees are withheld from the destination, so record what arrived
+    ctx.accounts.vault_token.reload()?;
+    let received = ctx.accounts.vault_token.amount
+        .checked_sub(balance_before)
+        .ok_or(VaultError::InvalidAmount)?;
+
+    // A fresh totals PDA opens a token position, which close_vault waits for
+    let totals = &mut ctx.accounts.totals;
+    if totals.vault == Pubkey::default() {
+        ctx.accounts.vault.token_positions = ctx.accounts.vault.token_positions
+            .checked_add(1)
+            .ok_or(VaultError::InvalidAmount)?;
+    }
+    totals.vault = ctx.accounts.vault.key();
+    totals.mint = ctx.accounts.mint.key();
+    totals.total_deposited = totals.total_deposited
+        .checked_add(received)
+        .ok_or(VaultError::InvalidAmount)?;
+
+    // Owner activity resets the dead-man's switch
+    let clock = Clock::get()?;
+    ctx.accounts.vault.touch(clock.unix_timestamp);
+
+    emit_cpi!(TokenDeposited {
+        vault: ctx.accounts.vault.key(),
+        owner: ctx.accounts.vault.owner,
+        mint: ctx.accounts.totals.mint,
+        depositor: ctx.accounts.signer.key(),
+        amount: received,
+        total_deposited: ctx.accounts.totals.total_deposited,
+        total_withdrawn: ctx.accounts.totals.total_withdrawn,
+        slot: clock.slot,
+        unix_timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,18 @@
+use anchor_lang::prelude::*;
+use crate::contexts::EnablePooling;
+use crate::errors::VaultError;
+
+pub fn handler(ctx: Context<EnablePooling>) -> Result<()> {
+    let vault = &mut ctx.accounts.vault;
+    let balance = vault.to_account_info().lamports();
+
+    // Anything already in the vault would be handed to the first depositor's shares,
+    // and open grants could still pay out on the owner's behalf
+    require!(vault.pool_value(balance)? == 0, VaultError::VaultNotEmpty);
+    require!(vault.grant_count == 0, VaultError::GrantsOutstanding);
+
+    // One-way: from now on the owner cannot withdraw or close the vault
+    vault.pooled = true;
+
+    Ok(())
+}
@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+use crate::contexts::ExecuteProposal;
+use crate::state::{HistoryKind, MultisigAction, VaultHistory};
+use crate::errors::VaultError;
+use crate::instructions::withdraw::pay_out;
+use crate::events::Withdrawn;
+
+pub fn handler(ctx: Context<ExecuteProposal>) -> Result<()> {
+    let multisig = &ctx.accounts.multisig;
+    let proposal = &ctx.accounts.proposal;
+
+    require!(!proposal.executed, VaultError::ProposalAlreadyExecuted);
+
+    // Only approvals from current members count towards the threshold
+    let approvals = proposal.approvals
+        .iter()
+        .filter(|approver| multisig.is_member(approver))
+        .count();
+    require!(
+        approvals >= multisig.threshold as usize,
+        VaultError::NotEnoughApprovals
+    );
+
+    match proposal.action {
+        MultisigAction::Withdraw { recipient, amount } => {
+            let recipient_info = ctx.accounts.recipient
+                .as_ref()
+                .ok_or(VaultError::InvalidDestination)?
+                .to_account_info();
+            require_keys_eq!(recipient_info.key(), recipient, VaultError::InvalidDestination);
+
+            // Multisig vaults never enforce the allowlist - it must be off before the handover
+            pay_out(&mut ctx.accounts.vault, &recipient_info, amount, None)?;
+
+            VaultHistory::record(
+                &ctx.accounts.history,
+                HistoryKind::Withdrawal,
+                amount,
+                recipient,
+                &ctx.accounts.vault.to_account_info(),
+            )?;
+
+            emit_cpi!(Withdrawn::new(
+                &ctx.accounts.vault,
+                ctx.accounts.signer.key(),
+                recipient,
+                amount,
+                &Clock::get()?,
+            ));
+        }
+        MultisigAction::AddMember { member } => {
+            ctx.accounts.multisig.members.push(member);
+        }
+        MultisigAction::RemoveMember { member } => {
+            ctx.accounts.multisig.members.retain(|m| *m != member);
+        }
+        MultisigAction::ChangeThreshold { threshold } => {
+            ctx.accounts.multisig.threshold = threshold;
+        }
+    }
+
+    // Config changes must leave the multisig usable
+    ctx.accounts.multisig.validate()?;
+
+    ctx.accounts.proposal.executed = true;
+
+    Ok(())
+}
@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+use crate::contexts::ExecuteSpendProposal;
+use crate::errors::VaultError;
+use crate::events::Withdrawn;
+use crate::instructions::withdraw::pay_out;
+use crate::state::{HistoryKind, VaultHistory};
+
+pub fn handler(ctx: Context<ExecuteSpendProposal>) -> Result<()> {
+    let clock = Clock::get()?;
+    let governance = &ctx.accounts.governance;
+    let proposal = &ctx.accounts.proposal;
+
+    require!(!proposal.executed, VaultError::ProposalAlreadyExecuted);
+    let executable_at = proposal.voting_ends_at
+        .checked_add(governance.execution_delay)
+        .ok_or(VaultError::InvalidAmount)?;
+    require!(
+        clock.unix_timestamp >= executable_at,
+        VaultError::ProposalNotReady
+    );
+    require!(
+        proposal.passed(governance.quorum_weight),
+        VaultError::ProposalNotPassed
+    );
+
+    let amount = proposal.amount;
+    let recipient_info = ctx.accounts.recipient.to_account_info();
+    // Governed vaults never enforce the allowlist - it must be off before the handover
+    pay_out(&mut ctx.accounts.vault, &recipient_info, amount, None)?;
+
+    ctx.accounts.proposal.executed = true;
+
+    VaultHistory::record(
+        &ctx.accounts.history,
+        HistoryKind::Withdrawal,
+        amount,
+        recipient_info.key(),
+        &ctx.accounts.vault.to_account_info(),
+    )?;
+
+    emit_cpi!(Withdrawn::new(
+        &ctx.accounts.vault,
+        ctx.accounts.signer.key(),
+        recipient_info.key(),
+        amount,
+        &clock,
+    ));
+
+    Ok(())
+}
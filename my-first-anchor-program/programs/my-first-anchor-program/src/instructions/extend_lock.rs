@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+use crate::contexts::LockAction;
+use crate::state::VaultLock;
+use crate::errors::VaultError;
+use crate::events::LockChanged;
+
+pub fn handler(ctx: Context<LockAction>, lock: VaultLock) -> Result<()> {
+    // The unlock point can only move later, never earlier or to a different lock kind
+    require!(
+        ctx.accounts.vault.lock.is_extended_by(&lock),
+        VaultError::LockNotExtended
+    );
+
+    ctx.accounts.vault.lock = lock;
+
+    let clock = Clock::get()?;
+    emit_cpi!(LockChanged {
+        vault: ctx.accounts.vault.key(),
+        owner: ctx.accounts.vault.owner,
+        lock,
+        slot: clock.slot,
+        unix_timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+use crate::contexts::FinishRecovery;
+use crate::errors::VaultError;
+use crate::events::OwnerChanged;
+use crate::state::RECOVERY_CHALLENGE_SECONDS;
+
+pub fn handler(ctx: Context<FinishRecovery>) -> Result<()> {
+    let clock = Clock::get()?;
+    let guardian_set = &mut ctx.accounts.guardian_set;
+    let recovery = guardian_set.recovery
+        .as_ref()
+        .ok_or(VaultError::NoRecoveryInProgress)?;
+
+    // Only approvals from current guardians count
+    let approvals = recovery.approvals
+        .iter()
+        .filter(|key| guardian_set.is_guardian(key))
+        .count();
+    require!(
+        approvals >= guardian_set.threshold as usize,
+        VaultError::RecoveryNotReady
+    );
+    require!(
+        clock.unix_timestamp >= recovery.started_at.saturating_add(RECOVERY_CHALLENGE_SECONDS),
+        VaultError::RecoveryNotReady
+    );
+
+    let new_owner = recovery.new_owner;
+    guardian_set.recovery = None;
+
+    // Same effect as accept_owner: the PDA follows `vault.creator`, so the address and
+    // lamports stay put and every owner check (withdraw included) now expects the new key
+    let vault = &mut ctx.accounts.vault;
+    let previous_owner = vault.owner;
+    vault.owner = new_owner;
+    vault.pending_owner = None;
+    vault.touch(clock.unix_timestamp);
+
+    emit_cpi!(OwnerChanged {
+        vault: ctx.accounts.vault.key(),
+        previous_owner,
+        new_owner,
+        slot: clock.slot,
+        unix_timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
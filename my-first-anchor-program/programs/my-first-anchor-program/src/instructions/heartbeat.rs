@@ -0,0 +1,9 @@
+use anchor_lang::prelude::*;
+use crate::contexts::InheritanceAction;
+
+pub fn handler(ctx: Context<InheritanceAction>) -> Result<()> {
+    // Deposits and withdrawals refresh the timestamp too - this is for idle cold storage
+    ctx.accounts.vault.touch(Clock::get()?.unix_timestamp);
+
+    Ok(())
+}
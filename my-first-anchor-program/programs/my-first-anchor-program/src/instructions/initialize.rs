@@ -1,9 +1,53 @@
 use anchor_lang::prelude::*;
+use anchor_spl::token::{mint_to, MintTo};
 use crate::contexts::InitializeVault;
+use crate::errors::VaultError;
+use crate::state::Vault;
+use crate::events::VaultInitialized;
 
 pub fn handler(ctx: Context<InitializeVault>) -> Result<()> {
-    ctx.accounts.vault.owner = ctx.accounts.signer.key();
-    ctx.accounts.vault.total_deposits = 0;
-    ctx.accounts.vault.total_withdrawals = 0;
+    // The default vault has an empty name, so its PDA stays [b"vault", signer]
+    let mut vault = Vault::new(ctx.accounts.signer.key(), String::new());
+
+    // Tokenized vault: mint the single ownership token to the signer, then hand ownership
+    // to the mint - from now on whoever holds the token controls the vault
+    if let Some(ownership_mint) = &ctx.accounts.ownership_mint {
+        let (Some(ownership_token), Some(token_program)) =
+            (&ctx.accounts.ownership_token, &ctx.accounts.token_program)
+        else {
+            return err!(VaultError::OwnershipAccountsRequired);
+        };
+
+        let signer_key = ctx.accounts.signer.key();
+        let vault_bump = [ctx.bumps.vault];
+        let vault_seeds: &[&[u8]] = &[b"vault", signer_key.as_ref(), &vault_bump];
+        mint_to(
+            CpiContext::new_with_signer(
+                token_program.to_account_info(),
+                MintTo {
+                    mint: ownership_mint.to_account_info(),
+                    to: ownership_token.to_account_info(),
+                    authority: ctx.accounts.vault.to_account_info(),
+                },
+                &[vault_seeds],
+            ),
+            1,
+        )?;
+
+        vault.owner = ownership_mint.key();
+        vault.tokenized = true;
+    }
+
+    ctx.accounts.vault.set_inner(vault);
+
+    let clock = Clock::get()?;
+    emit_cpi!(VaultInitialized {
+        vault: ctx.accounts.vault.key(),
+        owner: ctx.accounts.vault.owner,
+        name: String::new(),
+        slot: clock.slot,
+        unix_timestamp: clock.unix_timestamp,
+    });
+
     Ok(())
 }
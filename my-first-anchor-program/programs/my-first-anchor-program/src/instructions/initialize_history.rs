@@ -0,0 +1,14 @@
+use anchor_lang::prelude::*;
+use crate::contexts::InitializeHistory;
+
+pub fn handler(ctx: Context<InitializeHistory>) -> Result<()> {
+    // Zero-copy accounts are initialized with load_init() instead of field assignment
+    let mut history = ctx.accounts.history.load_init()?;
+    history.vault = ctx.accounts.vault.key();
+    history.head = 0;
+    history.count = 0;
+
+    // From now on every recording instruction must pass the history account
+    ctx.accounts.vault.history_attached = true;
+    Ok(())
+}
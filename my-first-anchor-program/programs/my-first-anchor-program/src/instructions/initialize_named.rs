@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+use crate::contexts::InitializeNamedVault;
+use crate::state::{Vault, MAX_INDEXED_VAULTS, MAX_VAULT_NAME_LEN};
+use crate::errors::VaultError;
+use crate::events::VaultInitialized;
+
+// Run through #[access_control] on `initialize_named`
+pub fn validate_name(name: &str) -> Result<()> {
+    // An empty name is the default vault - that one is created through `initialize`
+    require!(
+        !name.is_empty() && name.len() <= MAX_VAULT_NAME_LEN,
+        VaultError::InvalidVaultName
+    );
+    Ok(())
+}
+
+pub fn handler(ctx: Context<InitializeNamedVault>, name: String) -> Result<()> {
+    require!(
+        ctx.accounts.owner_index.vaults.len() < MAX_INDEXED_VAULTS,
+        VaultError::OwnerIndexFull
+    );
+
+    let vault = Vault::new(ctx.accounts.signer.key(), name);
+    ctx.accounts.vault.set_inner(vault);
+
+    // Register the vault so clients can list it from the index
+    let vault_key = ctx.accounts.vault.key();
+    ctx.accounts.owner_index.vaults.push(vault_key);
+
+    let clock = Clock::get()?;
+    emit_cpi!(VaultInitialized {
+        vault: vault_key,
+        owner: ctx.accounts.vault.owner,
+        name: ctx.accounts.vault.name.clone(),
+        slot: clock.slot,
+        unix_timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,8 @@
+use anchor_lang::prelude::*;
+use crate::contexts::InitializeOwnerIndex;
+
+pub fn handler(ctx: Context<InitializeOwnerIndex>) -> Result<()> {
+    ctx.accounts.owner_index.owner = ctx.accounts.signer.key();
+    ctx.accounts.owner_index.vaults = Vec::new();
+    Ok(())
+}
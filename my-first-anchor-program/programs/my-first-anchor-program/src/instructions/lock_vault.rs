@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+use crate::contexts::LockAction;
+use crate::state::VaultLock;
+use crate::errors::VaultError;
+use crate::events::LockChanged;
+
+pub fn handler(ctx: Context<LockAction>, lock: VaultLock) -> Result<()> {
+    let clock = Clock::get()?;
+
+    // Only one lock at a time - an active lock can only be changed through extend_lock
+    require!(
+        !ctx.accounts.vault.lock.is_active(&clock),
+        VaultError::LockAlreadyActive
+    );
+
+    // A lock that has already expired (or VaultLock::None) would not commit to anything
+    require!(lock.is_active(&clock), VaultError::InvalidLock);
+
+    ctx.accounts.vault.lock = lock;
+
+    emit_cpi!(LockChanged {
+        vault: ctx.accounts.vault.key(),
+        owner: ctx.accounts.vault.owner,
+        lock,
+        slot: clock.slot,
+        unix_timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
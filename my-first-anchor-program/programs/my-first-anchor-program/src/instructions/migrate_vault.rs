@@ -0,0 +1,126 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use crate::contexts::MigrateVault;
+use crate::errors::VaultError;
+use crate::events::VaultMigrated;
+use crate::state::{Vault, VaultV0, VAULT_V0_DISCRIMINATOR, VAULT_VERSION};
+
+pub fn handler(ctx: Context<MigrateVault>) -> Result<()> {
+    let vault_info = ctx.accounts.vault.to_account_info();
+
+    // What Account<Vault> would check, minus the deserialization
+    require_keys_eq!(*vault_info.owner, crate::ID, VaultError::InvalidVaultAccount);
+
+    let (from_version, vault) = parse_old_layout(&vault_info.try_borrow_data()?)?;
+
+    require!(
+        vault.is_owner(&ctx.accounts.signer.key(), ctx.accounts.ownership_token.as_ref()),
+        VaultError::Unauthorized
+    );
+
+    // The owner pays the extra rent - deposited lamports must not end up as the rent reserve
+    let rent = Rent::get()?;
+    let old_len = vault_info.data_len();
+    let new_len = Vault::INIT_SPACE + Vault::DISCRIMINATOR.len();
+    let top_up = rent
+        .minimum_balance(new_len)
+        .saturating_sub(rent.minimum_balance(old_len));
+    if top_up > 0 {
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.signer.to_account_info(),
+                to: vault_info.clone(),
+            },
+        );
+        transfer(cpi_context, top_up)?;
+    }
+
+    vault_info.resize(new_len)?;
+    vault.try_serialize(&mut &mut vault_info.try_borrow_mut_data()?[..])?;
+
+    let clock = Clock::get()?;
+    emit_cpi!(VaultMigrated {
+        vault: vault_info.key(),
+        owner: vault.owner,
+        from_version,
+        to_version: VAULT_VERSION,
+        slot: clock.slot,
+        unix_timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+// Reads a vault stored in an older layout (discriminator included) and returns its
+// version together with the equivalent current-layout Vault
+// Version 0 is the only layout deployed before this one - it has no version byte and is
+// recognized by its discriminator and size
+pub fn parse_old_layout(data: &[u8]) -> Result<(u8, Vault)> {
+    if data.starts_with(Vault::DISCRIMINATOR) {
+        return err!(VaultError::VaultUpToDate);
+    }
+    require!(
+        data.starts_with(&VAULT_V0_DISCRIMINATOR) && data.len() == VaultV0::LEN,
+        VaultError::InvalidVaultAccount
+    );
+
+    let legacy = VaultV0::deserialize(&mut &data[8..])?;
+    // Old vaults were always keyed by their owner, with no name
+    let vault = Vault {
+        total_deposits: legacy.total_deposits,
+        total_withdrawals: legacy.total_withdrawals,
+        ..Vault::new(legacy.owner, String::new())
+    };
+    Ok((0, vault))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn with_discriminator(discriminator: &[u8], body: &[u8]) -> Vec<u8> {
+        [discriminator, body].concat()
+    }
+
+    #[test]
+    fn parses_v0_layout() {
+        let owner = Pubkey::new_unique();
+        let v0 = VaultV0 { owner, total_deposits: 1_000, total_withdrawals: 400 };
+        let data = with_discriminator(&VAULT_V0_DISCRIMINATOR, &v0.try_to_vec().unwrap());
+        assert_eq!(data.len(), VaultV0::LEN);
+
+        let (from_version, vault) = parse_old_layout(&data).unwrap();
+        assert_eq!(from_version, 0);
+        assert_eq!(vault.version, VAULT_VERSION);
+        assert_eq!(vault.owner, owner);
+        assert_eq!(vault.creator, owner);
+        assert!(vault.name.is_empty());
+        assert_eq!(vault.total_deposits, 1_000);
+        assert_eq!(vault.total_withdrawals, 400);
+        assert_eq!(vault.grant_count, 0);
+        assert!(!vault.pooled);
+        assert!(!vault.allowlist_enforced);
+        assert!(!vault.history_attached);
+        assert_eq!(vault.token_positions, 0);
+        assert!(!vault.tokenized);
+    }
+
+    #[test]
+    fn rejects_current_layout() {
+        let mut data = Vault::new(Pubkey::new_unique(), String::new()).try_to_vec().unwrap();
+        data.splice(0..0, Vault::DISCRIMINATOR.iter().copied());
+        assert_eq!(parse_old_layout(&data).err(), Some(VaultError::VaultUpToDate.into()));
+    }
+
+    #[test]
+    fn rejects_unknown_layouts() {
+        // Version 0 discriminator with the wrong size
+        let data = with_discriminator(&VAULT_V0_DISCRIMINATOR, &[0; 64]);
+        assert_eq!(parse_old_layout(&data).err(), Some(VaultError::InvalidVaultAccount.into()));
+
+        // Some other account type
+        let data = with_discriminator(&[0; 8], &[0; 48]);
+        assert_eq!(parse_old_layout(&data).err(), Some(VaultError::InvalidVaultAccount.into()));
+    }
+}
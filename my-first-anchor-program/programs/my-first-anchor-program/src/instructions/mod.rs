@@ -2,3 +2,63 @@ pub mod initialize;
 pub mod deposit;
 pub mod withdraw;
 pub mod read_vault;
+pub mod lock_vault;
+pub mod extend_lock;
+pub mod add_delegate;
+pub mod revoke_delegate;
+pub mod delegate_withdraw;
+pub mod create_multisig;
+pub mod create_proposal;
+pub mod approve_proposal;
+pub mod execute_proposal;
+pub mod close_vault;
+pub mod propose_owner;
+pub mod accept_owner;
+pub mod set_withdrawal_limit;
+pub mod apply_withdrawal_limit;
+pub mod initialize_owner_index;
+pub mod initialize_named;
+pub mod deposit_to;
+pub mod create_stream;
+pub mod claim_stream;
+pub mod cancel_stream;
+pub mod initialize_history;
+pub mod delegate_stake;
+pub mod deactivate_stake;
+pub mod withdraw_stake;
+pub mod set_guardians;
+pub mod remove_guardians;
+pub mod start_recovery;
+pub mod approve_recovery;
+pub mod cancel_recovery;
+pub mod finish_recovery;
+pub mod set_beneficiary;
+pub mod heartbeat;
+pub mod claim_inheritance;
+pub mod withdraw_signed;
+pub mod create_session;
+pub mod revoke_session;
+pub mod session_deposit;
+pub mod session_withdraw;
+pub mod migrate_vault;
+pub mod enable_pooling;
+pub mod pool_deposit;
+pub mod pool_withdraw;
+pub mod add_destination;
+pub mod remove_destination;
+pub mod withdraw_to;
+pub mod create_governance;
+pub mod add_governance_member;
+pub mod activate_governance;
+pub mod create_spend_proposal;
+pub mod cast_vote;
+pub mod execute_spend_proposal;
+pub mod create_subscription;
+pub mod collect_subscription;
+pub mod cancel_subscription;
+pub mod transfer_between_vaults;
+pub mod deposit_token;
+pub mod withdraw_token;
+pub mod set_allowlist_enforced;
+pub mod apply_allowlist_change;
+pub mod close_token_account;
@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use crate::contexts::PoolDeposit;
+use crate::errors::VaultError;
+use crate::events::Deposited;
+use crate::state::{HistoryKind, VaultHistory};
+
+pub fn handler(ctx: Context<PoolDeposit>, amount: u64) -> Result<()> {
+    require!(amount > 0, VaultError::InvalidAmount);
+
+    // Price the shares before the deposit lands
+    let vault = &ctx.accounts.vault;
+    let pool_value = vault.pool_value(vault.to_account_info().lamports())?;
+    let shares = vault.shares_for_deposit(amount, pool_value)?;
+    require!(shares > 0, VaultError::InvalidAmount);
+
+    let cpi_context = CpiContext::new(
+        ctx.accounts.system_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.signer.to_account_info(),
+            to: ctx.accounts.vault.to_account_info(),
+        },
+    );
+    transfer(cpi_context, amount)?;
+
+    let vault = &mut ctx.accounts.vault;
+    vault.total_deposits = vault.total_deposits
+        .checked_add(amount)
+        .ok_or(VaultError::InvalidAmount)?;
+    vault.total_shares = vault.total_shares
+        .checked_add(shares)
+        .ok_or(VaultError::InvalidAmount)?;
+
+    // The shares account is created on the first deposit
+    let pool_shares = &mut ctx.accounts.pool_shares;
+    pool_shares.vault = ctx.accounts.vault.key();
+    pool_shares.depositor = ctx.accounts.signer.key();
+    pool_shares.shares = pool_shares.shares
+        .checked_add(shares)
+        .ok_or(VaultError::InvalidAmount)?;
+
+    VaultHistory::record(
+        &ctx.accounts.history,
+        HistoryKind::Deposit,
+        amount,
+        ctx.accounts.signer.key(),
+        &ctx.accounts.vault.to_account_info(),
+    )?;
+
+    emit_cpi!(Deposited::new(
+        &ctx.accounts.vault,
+        ctx.accounts.signer.key(),
+        amount,
+        &Clock::get()?,
+    ));
+
+    Ok(())
+}
@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+use crate::contexts::PoolWithdraw;
+use crate::errors::VaultError;
+use crate::events::Withdrawn;
+use crate::instructions::withdraw::move_lamports;
+use crate::state::{HistoryKind, VaultHistory};
+
+pub fn handler(ctx: Context<PoolWithdraw>, shares: u64) -> Result<()> {
+    require!(shares > 0, VaultError::InvalidAmount);
+    require!(
+        shares <= ctx.accounts.pool_shares.shares,
+        VaultError::InsufficientShares
+    );
+
+    let vault = &ctx.accounts.vault;
+    let pool_value = vault.pool_value(vault.to_account_info().lamports())?;
+    let amount = vault.lamports_for_shares(shares, pool_value)?;
+
+    ctx.accounts.pool_shares.shares -= shares;
+    ctx.accounts.vault.total_shares -= shares;
+
+    // The owner's lock and withdrawal limit do not apply, and pooled vaults cannot stake,
+    // so shareholders can always exit
+    let signer_info = ctx.accounts.signer.to_account_info();
+    move_lamports(&ctx.accounts.vault, &signer_info, amount)?;
+
+    ctx.accounts.vault.total_withdrawals = ctx.accounts.vault.total_withdrawals
+        .checked_add(amount)
+        .ok_or(VaultError::InvalidAmount)?;
+
+    VaultHistory::record(
+        &ctx.accounts.history,
+        HistoryKind::Withdrawal,
+        amount,
+        signer_info.key(),
+        &ctx.accounts.vault.to_account_info(),
+    )?;
+
+    emit_cpi!(Withdrawn::new(
+        &ctx.accounts.vault,
+        signer_info.key(),
+        signer_info.key(),
+        amount,
+        &Clock::get()?,
+    ));
+
+    Ok(())
+}
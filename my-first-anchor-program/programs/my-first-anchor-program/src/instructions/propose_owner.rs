@@ -0,0 +1,10 @@
+use anchor_lang::prelude::*;
+use crate::contexts::ProposeOwner;
+
+pub fn handler(ctx: Context<ProposeOwner>, new_owner: Option<Pubkey>) -> Result<()> {
+    // Nothing changes until the new owner signs accept_owner,
+    // so a typo in the address cannot lock the owner out
+    ctx.accounts.vault.pending_owner = new_owner;
+
+    Ok(())
+}
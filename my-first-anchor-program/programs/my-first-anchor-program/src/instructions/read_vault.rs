@@ -1,31 +1,43 @@
 use anchor_lang::prelude::*;
 use crate::contexts::ReadVaultAction;
-use crate::state::LazyVault; // Import the auto-generated LazyVault trait
-use crate::errors::VaultError;
+use crate::state::{LazyVault, Vault, VaultSnapshot}; // LazyVault is the auto-generated LazyAccount trait
 
-// Example instruction showing LazyAccount usage for read-only operations
-pub fn handler(ctx: Context<ReadVaultAction>) -> Result<()> {
+// View function: returns a VaultSnapshot through return data (Anchor calls set_return_data
+// for the program's return value), so other programs can CPI into it and clients can simulate it
+pub fn handler(ctx: Context<ReadVaultAction>) -> Result<VaultSnapshot> {
     // Use LazyAccount to read vault data selectively (read-only, heap-allocated)
     // This is more efficient than deserializing the entire account onto the stack
     // Methods are generated as load_<field_name>() for each field
-    let vault_owner = ctx.accounts.vault.load_owner()?;
-    let _total_deposits = ctx.accounts.vault.load_total_deposits()?;
-    let _total_withdrawals = ctx.accounts.vault.load_total_withdrawals()?;
-    
-    // You can use the data for validation or logging
-    // Note: load_owner() returns a reference, so we need to dereference it for comparison
-    require!(
-        *vault_owner == ctx.accounts.signer.key(),
-        VaultError::InvalidAmount
-    );
-    
-    // Example: You can log the values (they're loaded lazily, only when accessed)
-    // The _ prefix indicates we're intentionally not using these values
-    let _ = _total_deposits;
-    let _ = _total_withdrawals;
-    
-    // LazyAccount is read-only - you cannot mutate fields
-    // For mutations, use Account<'info, Vault> instead
-    
-    Ok(())
+    let vault = &ctx.accounts.vault;
+    let clock = Clock::get()?;
+
+    // Liquid lamports sit in the vault account itself, staked lamports in its stake accounts
+    let balance = vault.to_account_info().lamports();
+    let rent_floor = Vault::rent_floor()?;
+
+    // What the owner could withdraw right now: everything above the rent floor,
+    // nothing while locked or pooled, and no more than the withdrawal limit still allows
+    let mut withdrawable = balance.saturating_sub(rent_floor);
+    if vault.load_lock()?.is_active(&clock) || *vault.load_pooled()? {
+        withdrawable = 0;
+    }
+    if let Some(limit) = *vault.load_withdrawal_limit()? {
+        let remaining = limit.remaining(
+            *vault.load_window_start()?,
+            *vault.load_window_withdrawn()?,
+            clock.unix_timestamp,
+        );
+        withdrawable = withdrawable.min(remaining);
+    }
+
+    Ok(VaultSnapshot {
+        vault: vault.key(),
+        owner: *vault.load_owner()?,
+        balance,
+        staked_lamports: *vault.load_staked_lamports()?,
+        rent_floor,
+        withdrawable,
+        total_deposits: *vault.load_total_deposits()?,
+        total_withdrawals: *vault.load_total_withdrawals()?,
+    })
 }
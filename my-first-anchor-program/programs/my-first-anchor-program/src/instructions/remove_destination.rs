@@ -0,0 +1,23 @@
+use anchor_lang::prelude::*;
+use crate::contexts::RemoveDestination;
+use crate::errors::VaultError;
+use crate::events::DestinationRemoved;
+
+pub fn handler(ctx: Context<RemoveDestination>) -> Result<()> {
+    // The entry is closed by the `close = signer` constraint - removing only narrows
+    // where funds can go, so there is no delay
+    ctx.accounts.vault.grant_count = ctx.accounts.vault.grant_count
+        .checked_sub(1)
+        .ok_or(VaultError::InvalidAmount)?;
+
+    let clock = Clock::get()?;
+    emit_cpi!(DestinationRemoved {
+        vault: ctx.accounts.vault.key(),
+        owner: ctx.accounts.vault.owner,
+        destination: ctx.accounts.allowed_destination.destination,
+        slot: clock.slot,
+        unix_timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,13 @@
+use anchor_lang::prelude::*;
+use crate::contexts::RemoveGuardians;
+use crate::errors::VaultError;
+
+pub fn handler(ctx: Context<RemoveGuardians>) -> Result<()> {
+    // The guardian set is closed by the `close = signer` constraint,
+    // which also drops any recovery in progress
+    ctx.accounts.vault.grant_count = ctx.accounts.vault.grant_count
+        .checked_sub(1)
+        .ok_or(VaultError::InvalidAmount)?;
+
+    Ok(())
+}
@@ -0,0 +1,13 @@
+use anchor_lang::prelude::*;
+use crate::contexts::RevokeDelegate;
+use crate::errors::VaultError;
+
+pub fn handler(ctx: Context<RevokeDelegate>) -> Result<()> {
+    // The delegate PDA is closed by the `close = signer` constraint,
+    // which also refunds its rent to the owner
+    ctx.accounts.vault.grant_count = ctx.accounts.vault.grant_count
+        .checked_sub(1)
+        .ok_or(VaultError::InvalidAmount)?;
+
+    Ok(())
+}
@@ -0,0 +1,24 @@
+use anchor_lang::prelude::*;
+use crate::contexts::RevokeSession;
+use crate::errors::VaultError;
+
+pub fn handler(ctx: Context<RevokeSession>) -> Result<()> {
+    // Expired sessions are already unusable - letting anyone close them lets a crank
+    // clean up after the owner and keeps grant_count from blocking close_vault
+    let expired = Clock::get()?.unix_timestamp >= ctx.accounts.vault_session.expires_at;
+    require!(
+        expired
+            || ctx.accounts.vault.is_owner(
+                &ctx.accounts.signer.key(),
+                ctx.accounts.ownership_token.as_ref(),
+            ),
+        VaultError::Unauthorized
+    );
+
+    // The session PDA is closed by the `close = owner` constraint
+    ctx.accounts.vault.grant_count = ctx.accounts.vault.grant_count
+        .checked_sub(1)
+        .ok_or(VaultError::InvalidAmount)?;
+
+    Ok(())
+}
@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use crate::contexts::SessionDeposit;
+use crate::errors::VaultError;
+use crate::events::Deposited;
+use crate::state::{HistoryKind, SessionScope, VaultHistory};
+
+pub fn handler(ctx: Context<SessionDeposit>, amount: u64) -> Result<()> {
+    let clock = Clock::get()?;
+    let vault_session = &ctx.accounts.vault_session;
+
+    require!(
+        clock.unix_timestamp < vault_session.expires_at,
+        VaultError::SessionExpired
+    );
+    require!(
+        vault_session.scope == SessionScope::DepositOnly,
+        VaultError::SessionNotPermitted
+    );
+
+    let cpi_context = CpiContext::new(
+        ctx.accounts.system_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.signer.to_account_info(),
+            to: ctx.accounts.vault.to_account_info(),
+        },
+    );
+    transfer(cpi_context, amount)?;
+
+    ctx.accounts.vault.total_deposits = ctx.accounts.vault.total_deposits
+        .checked_add(amount)
+        .ok_or(VaultError::InvalidAmount)?;
+
+    VaultHistory::record(
+        &ctx.accounts.history,
+        HistoryKind::Deposit,
+        amount,
+        ctx.accounts.signer.key(),
+        &ctx.accounts.vault.to_account_info(),
+    )?;
+
+    emit_cpi!(Deposited::new(
+        &ctx.accounts.vault,
+        ctx.accounts.signer.key(),
+        amount,
+        &clock,
+    ));
+
+    Ok(())
+}
@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+use crate::contexts::SessionWithdraw;
+use crate::errors::VaultError;
+use crate::events::Withdrawn;
+use crate::instructions::withdraw::pay_out;
+use crate::state::{HistoryKind, SessionScope, VaultHistory};
+
+pub fn handler(ctx: Context<SessionWithdraw>, amount: u64) -> Result<()> {
+    let clock = Clock::get()?;
+    let vault_session = &mut ctx.accounts.vault_session;
+
+    require!(
+        clock.unix_timestamp < vault_session.expires_at,
+        VaultError::SessionExpired
+    );
+    let SessionScope::Withdraw { destination, cap } = vault_session.scope else {
+        return err!(VaultError::SessionNotPermitted);
+    };
+    require_keys_eq!(
+        ctx.accounts.destination.key(),
+        destination,
+        VaultError::InvalidDestination
+    );
+
+    let withdrawn = vault_session.withdrawn
+        .checked_add(amount)
+        .ok_or(VaultError::InvalidAmount)?;
+    require!(withdrawn <= cap, VaultError::SessionCapExceeded);
+    vault_session.withdrawn = withdrawn;
+
+    let destination_info = ctx.accounts.destination.to_account_info();
+    pay_out(
+        &mut ctx.accounts.vault,
+        &destination_info,
+        amount,
+        ctx.accounts.allowed_destination.as_ref(),
+    )?;
+
+    VaultHistory::record(
+        &ctx.accounts.history,
+        HistoryKind::Withdrawal,
+        amount,
+        destination_info.key(),
+        &ctx.accounts.vault.to_account_info(),
+    )?;
+
+    emit_cpi!(Withdrawn::new(
+        &ctx.accounts.vault,
+        ctx.accounts.signer.key(),
+        destination_info.key(),
+        amount,
+        &clock,
+    ));
+
+    Ok(())
+}
@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+use crate::contexts::AllowlistAction;
+use crate::events::AllowlistChanged;
+use crate::state::LIMIT_CHANGE_DELAY_SECONDS;
+
+pub fn handler(ctx: Context<AllowlistAction>, enforced: bool) -> Result<()> {
+    let clock = Clock::get()?;
+    let now = clock.unix_timestamp;
+    let vault = &mut ctx.accounts.vault;
+
+    // Enforcing can only narrow where funds go, so it applies right away (and drops a queued
+    // disable); turning it off widens it, so it waits out the same timelock as a looser limit
+    let effective_at = if enforced || !vault.allowlist_enforced {
+        vault.allowlist_enforced = enforced;
+        vault.allowlist_disable_at = None;
+        now
+    } else {
+        let effective_at = now + LIMIT_CHANGE_DELAY_SECONDS;
+        vault.allowlist_disable_at = Some(effective_at);
+        effective_at
+    };
+
+    emit_cpi!(AllowlistChanged {
+        vault: ctx.accounts.vault.key(),
+        owner: ctx.accounts.vault.owner,
+        enforced,
+        effective_at,
+        slot: clock.slot,
+        unix_timestamp: now,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+use crate::contexts::InheritanceAction;
+use crate::errors::VaultError;
+use crate::events::BeneficiaryChanged;
+
+pub fn handler(
+    ctx: Context<InheritanceAction>,
+    beneficiary: Option<Pubkey>,
+    inactivity_period: i64,
+) -> Result<()> {
+    // A beneficiary is bound to a key and would outlive a sale of the ownership token
+    require!(
+        beneficiary.is_none() || !ctx.accounts.vault.tokenized,
+        VaultError::VaultTokenized
+    );
+    require!(
+        beneficiary.is_none() || inactivity_period > 0,
+        VaultError::InvalidInheritanceConfig
+    );
+
+    let clock = Clock::get()?;
+    let vault = &mut ctx.accounts.vault;
+    vault.beneficiary = beneficiary;
+    vault.inactivity_period = if beneficiary.is_some() { inactivity_period } else { 0 };
+    // The period always counts from the latest configuration
+    vault.touch(clock.unix_timestamp);
+
+    emit_cpi!(BeneficiaryChanged {
+        vault: ctx.accounts.vault.key(),
+        owner: ctx.accounts.vault.owner,
+        beneficiary: ctx.accounts.vault.beneficiary,
+        inactivity_period: ctx.accounts.vault.inactivity_period,
+        slot: clock.slot,
+        unix_timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
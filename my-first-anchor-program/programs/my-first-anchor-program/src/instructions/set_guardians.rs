@@ -0,0 +1,19 @@
+use anchor_lang::prelude::*;
+use crate::contexts::SetGuardians;
+use crate::errors::VaultError;
+
+pub fn handler(ctx: Context<SetGuardians>, guardians: Vec<Pubkey>, threshold: u8) -> Result<()> {
+    let guardian_set = &mut ctx.accounts.guardian_set;
+    guardian_set.vault = ctx.accounts.vault.key();
+    guardian_set.guardians = guardians;
+    guardian_set.threshold = threshold;
+    guardian_set.recovery = None;
+    guardian_set.validate()?;
+
+    // Guardians can take over the vault, so they must be removed before it is closed
+    ctx.accounts.vault.grant_count = ctx.accounts.vault.grant_count
+        .checked_add(1)
+        .ok_or(VaultError::InvalidAmount)?;
+
+    Ok(())
+}
@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+use crate::contexts::WithdrawalLimitAction;
+use crate::state::{PendingLimitChange, WithdrawalLimit, LIMIT_CHANGE_DELAY_SECONDS};
+use crate::errors::VaultError;
+use crate::events::WithdrawalLimitChanged;
+
+pub fn handler(ctx: Context<WithdrawalLimitAction>, limit: Option<WithdrawalLimit>) -> Result<()> {
+    if let Some(new_limit) = limit {
+        require!(
+            new_limit.max_lamports > 0 && new_limit.window_seconds > 0,
+            VaultError::InvalidWithdrawalLimit
+        );
+    }
+
+    let clock = Clock::get()?;
+    let now = clock.unix_timestamp;
+    let vault = &mut ctx.accounts.vault;
+
+    // Tightening can only reduce what a leaked key could take, so it applies right away
+    let tightens = match (limit, vault.withdrawal_limit) {
+        (Some(new_limit), Some(current)) => new_limit.is_at_least_as_strict_as(&current),
+        (Some(_), None) => true,
+        (None, _) => false,
+    };
+
+    let effective_at = if tightens {
+        vault.withdrawal_limit = limit;
+        vault.pending_limit_change = None;
+        now
+    } else {
+        // Loosening or removing the limit waits out the timelock
+        let effective_at = now + LIMIT_CHANGE_DELAY_SECONDS;
+        vault.pending_limit_change = Some(PendingLimitChange { limit, effective_at });
+        effective_at
+    };
+
+    emit_cpi!(WithdrawalLimitChanged {
+        vault: ctx.accounts.vault.key(),
+        owner: ctx.accounts.vault.owner,
+        limit,
+        effective_at,
+        slot: clock.slot,
+        unix_timestamp: now,
+    });
+
+    Ok(())
+}
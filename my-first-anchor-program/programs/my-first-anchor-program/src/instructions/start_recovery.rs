@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+use crate::contexts::GuardianAction;
+use crate::errors::VaultError;
+use crate::events::RecoveryStarted;
+use crate::state::Recovery;
+
+pub fn handler(ctx: Context<GuardianAction>, new_owner: Pubkey) -> Result<()> {
+    let signer = ctx.accounts.signer.key();
+    let guardian_set = &mut ctx.accounts.guardian_set;
+
+    require!(guardian_set.is_guardian(&signer), VaultError::NotAGuardian);
+    require!(guardian_set.recovery.is_none(), VaultError::RecoveryInProgress);
+
+    // The challenge period starts now - the starting guardian counts as the first approval
+    let clock = Clock::get()?;
+    guardian_set.recovery = Some(Recovery {
+        new_owner,
+        approvals: vec![signer],
+        started_at: clock.unix_timestamp,
+    });
+
+    emit_cpi!(RecoveryStarted {
+        vault: ctx.accounts.guardian_set.vault,
+        guardian: signer,
+        new_owner,
+        slot: clock.slot,
+        unix_timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+use crate::contexts::TransferBetweenVaults;
+use crate::errors::VaultError;
+use crate::events::VaultTransferred;
+use crate::instructions::withdraw::pay_out;
+use crate::state::{HistoryKind, VaultHistory};
+
+pub fn handler(ctx: Context<TransferBetweenVaults>, amount: u64) -> Result<()> {
+    // Both vaults are owned by this program, so lamports are edited by hand on each side.
+    // The source goes through the usual lock, limit and rent checks.
+    let to_info = ctx.accounts.to_vault.to_account_info();
+    pay_out(
+        &mut ctx.accounts.from_vault,
+        &to_info,
+        amount,
+        ctx.accounts.allowed_destination.as_ref(),
+    )?;
+
+    ctx.accounts.to_vault.total_deposits = ctx.accounts.to_vault.total_deposits
+        .checked_add(amount)
+        .ok_or(VaultError::InvalidAmount)?;
+
+    // Owner activity resets the dead-man's switch
+    let clock = Clock::get()?;
+    ctx.accounts.from_vault.touch(clock.unix_timestamp);
+
+    let from_key = ctx.accounts.from_vault.key();
+    let to_key = ctx.accounts.to_vault.key();
+    VaultHistory::record(
+        &ctx.accounts.from_history,
+        HistoryKind::Withdrawal,
+        amount,
+        to_key,
+        &ctx.accounts.from_vault.to_account_info(),
+    )?;
+    VaultHistory::record(
+        &ctx.accounts.to_history,
+        HistoryKind::Deposit,
+        amount,
+        from_key,
+        &to_info,
+    )?;
+
+    emit_cpi!(VaultTransferred {
+        from_vault: from_key,
+        to_vault: to_key,
+        authority: ctx.accounts.signer.key(),
+        amount,
+        from_total_withdrawals: ctx.accounts.from_vault.total_withdrawals,
+        to_total_deposits: ctx.accounts.to_vault.total_deposits,
+        slot: clock.slot,
+        unix_timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
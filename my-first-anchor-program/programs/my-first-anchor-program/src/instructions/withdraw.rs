@@ -1,22 +1,90 @@
 use anchor_lang::prelude::*;
 use crate::contexts::WithdrawAction;
-use crate::state::Vault;
+use crate::state::{AllowedDestination, HistoryKind, Vault, VaultHistory};
 use crate::errors::VaultError;
+use crate::events::Withdrawn;
 
 pub fn handler(ctx: Context<WithdrawAction>, amount: u64) -> Result<()> {
-    // Verify the vault owner matches the signer
+    let signer_info = ctx.accounts.signer.to_account_info();
+    pay_out(
+        &mut ctx.accounts.vault,
+        &signer_info,
+        amount,
+        ctx.accounts.allowed_destination.as_ref(),
+    )?;
+    
+    // Owner activity resets the dead-man's switch
+    let clock = Clock::get()?;
+    ctx.accounts.vault.touch(clock.unix_timestamp);
+    
+    VaultHistory::record(
+        &ctx.accounts.history,
+        HistoryKind::Withdrawal,
+        amount,
+        signer_info.key(),
+        &ctx.accounts.vault.to_account_info(),
+    )?;
+    
+    emit_cpi!(Withdrawn::new(
+        &ctx.accounts.vault,
+        signer_info.key(),
+        signer_info.key(),
+        amount,
+        &clock,
+    ));
+    
+    Ok(())
+}
+
+// Shared payout path for every instruction that moves lamports out of a vault
+// on the owner's authority (pooled vaults are refused)
+// Enforces the destination allowlist, the withdrawal lock, the withdrawal limit and the
+// rent-exempt floor, then records the withdrawal - callers emit the Withdrawn event
+// (emit_cpi! needs their ctx)
+pub fn pay_out<'info>(
+    vault: &mut Account<'info, Vault>,
+    to: &AccountInfo<'info>,
+    amount: u64,
+    allowed_destination: Option<&Account<'info, AllowedDestination>>,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    
+    // The lamports of a pooled vault belong to its shareholders, not to the owner
+    require!(!vault.pooled, VaultError::VaultPooled);
+    
+    check_destination(vault, to.key, allowed_destination, clock.unix_timestamp)?;
+    
+    // Reject withdrawals until the clock passes the unlock point
     require!(
-        ctx.accounts.vault.owner == ctx.accounts.signer.key(),
-        VaultError::InvalidAmount
+        !vault.lock.is_active(&clock),
+        VaultError::VaultLocked
     );
     
+    // Count against the per-window withdrawal limit, if one is set
+    vault.consume_withdrawal_allowance(amount, clock.unix_timestamp)?;
+    
+    move_lamports(vault, to, amount)?;
+    
+    // Update vault metadata
+    vault.total_withdrawals = vault.total_withdrawals
+        .checked_add(amount)
+        .ok_or(VaultError::InvalidAmount)?;
+    
+    Ok(())
+}
+
+// Move lamports out of the vault without recording a withdrawal (e.g. into a stake
+// account that stays in program custody) - only the rent-exempt floor is enforced
+pub fn move_lamports<'info>(
+    vault: &Account<'info, Vault>,
+    to: &AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
     // Calculate rent-exempt minimum for the vault account
-    let rent = anchor_lang::solana_program::rent::Rent::get()?;
-    let min_rent = rent.minimum_balance(Vault::INIT_SPACE + Vault::DISCRIMINATOR.len());
+    let min_rent = Vault::rent_floor()?;
     
-    // Get account infos for lamport manipulation
-    let vault_info = ctx.accounts.vault.to_account_info();
-    let signer_info = ctx.accounts.signer.to_account_info();
+    // Get account info for lamport manipulation
+    let vault_info = vault.to_account_info();
     
     // Get current vault balance
     let vault_balance = vault_info.lamports();
@@ -27,16 +95,33 @@ pub fn handler(ctx: Context<WithdrawAction>, amount: u64) -> Result<()> {
         VaultError::InvalidAmount
     );
     
-    // Transfer lamports from vault back to signer
+    // Transfer lamports from vault to the recipient
     // We can't use system program's transfer on an account with data,
     // so we manually modify lamports
     **vault_info.try_borrow_mut_lamports()? -= amount;
-    **signer_info.try_borrow_mut_lamports()? += amount;
-    
-    // Update vault metadata
-    ctx.accounts.vault.total_withdrawals = ctx.accounts.vault.total_withdrawals
-        .checked_add(amount)
-        .ok_or(VaultError::InvalidAmount)?;
+    **to.try_borrow_mut_lamports()? += amount;
     
     Ok(())
 }
+
+// While the vault enforces its allowlist, `destination` must have an allowlist entry for
+// this vault whose delay has passed - a no-op otherwise
+pub fn check_destination(
+    vault: &Account<Vault>,
+    destination: &Pubkey,
+    allowed_destination: Option<&Account<AllowedDestination>>,
+    now: i64,
+) -> Result<()> {
+    if !vault.allowlist_enforced {
+        return Ok(());
+    }
+
+    let allowed = allowed_destination.ok_or(VaultError::DestinationNotAllowed)?;
+    require!(
+        allowed.vault == vault.key() && allowed.destination == *destination,
+        VaultError::DestinationNotAllowed
+    );
+    require!(now >= allowed.active_at, VaultError::DestinationNotActive);
+
+    Ok(())
+}
@@ -0,0 +1,98 @@
+use anchor_lang::prelude::*;
+use solana_instructions_sysvar::get_instruction_relative;
+use crate::contexts::WithdrawSigned;
+use crate::errors::VaultError;
+use crate::events::Withdrawn;
+use crate::instructions::withdraw::pay_out;
+use crate::state::{HistoryKind, VaultHistory, WithdrawalAuthorization, ED25519_PROGRAM_ID};
+
+pub fn handler(ctx: Context<WithdrawSigned>, authorization: WithdrawalAuthorization) -> Result<()> {
+    let clock = Clock::get()?;
+    require!(
+        clock.unix_timestamp <= authorization.expires_at,
+        VaultError::AuthorizationExpired
+    );
+
+    let mut message = Vec::new();
+    authorization.serialize(&mut message)?;
+    let authorizer = verify_ed25519_instruction(
+        &ctx.accounts.instructions.to_account_info(),
+        &message,
+    )?;
+    // For tokenized vaults the token holder signs - a sold vault invalidates the seller's authorizations
+    require!(
+        ctx.accounts.vault.is_owner(&authorizer, ctx.accounts.ownership_token.as_ref()),
+        VaultError::InvalidSignatureInstruction
+    );
+
+    ctx.accounts.vault.use_nonce(authorization.nonce)?;
+
+    let recipient_info = ctx.accounts.recipient.to_account_info();
+    pay_out(
+        &mut ctx.accounts.vault,
+        &recipient_info,
+        authorization.amount,
+        ctx.accounts.allowed_destination.as_ref(),
+    )?;
+
+    // The owner signed it, so it counts as owner activity for the dead-man's switch
+    ctx.accounts.vault.touch(clock.unix_timestamp);
+
+    VaultHistory::record(
+        &ctx.accounts.history,
+        HistoryKind::Withdrawal,
+        authorization.amount,
+        recipient_info.key(),
+        &ctx.accounts.vault.to_account_info(),
+    )?;
+
+    emit_cpi!(Withdrawn::new(
+        &ctx.accounts.vault,
+        authorizer,
+        recipient_info.key(),
+        authorization.amount,
+        &clock,
+    ));
+
+    Ok(())
+}
+
+// The Ed25519 program only checks signatures, it does not tell us what was signed -
+// so read the instruction right before this one and make sure it verified `message`,
+// with all of its data inside that instruction. Returns the key that signed it
+fn verify_ed25519_instruction(instructions: &AccountInfo, message: &[u8]) -> Result<Pubkey> {
+    let ix = get_instruction_relative(-1, instructions)
+        .map_err(|_| VaultError::InvalidSignatureInstruction)?;
+    require_keys_eq!(ix.program_id, ED25519_PROGRAM_ID, VaultError::InvalidSignatureInstruction);
+
+    // Layout: [num_signatures: u8, padding: u8, then one offsets struct of 7 u16s]
+    let data = &ix.data;
+    require!(
+        data.len() >= 16 && data[0] == 1,
+        VaultError::InvalidSignatureInstruction
+    );
+    let read_u16 = |at: usize| u16::from_le_bytes([data[at], data[at + 1]]);
+    let signature_instruction_index = read_u16(4);
+    let public_key_offset = read_u16(6) as usize;
+    let public_key_instruction_index = read_u16(8);
+    let message_offset = read_u16(10) as usize;
+    let message_size = read_u16(12) as usize;
+    let message_instruction_index = read_u16(14);
+
+    // u16::MAX means "this instruction" - anything else could point at data we never see
+    require!(
+        signature_instruction_index == u16::MAX
+            && public_key_instruction_index == u16::MAX
+            && message_instruction_index == u16::MAX,
+        VaultError::InvalidSignatureInstruction
+    );
+
+    let public_key = data
+        .get(public_key_offset..public_key_offset + 32)
+        .and_then(|key| Pubkey::try_from(key).ok())
+        .ok_or(VaultError::InvalidSignatureInstruction)?;
+    let signed_message = data.get(message_offset..message_offset + message_size);
+    require!(signed_message == Some(message), VaultError::InvalidSignatureInstruction);
+
+    Ok(public_key)
+}
@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::invoke_signed;
+use solana_stake_interface::instruction as stake_instruction;
+use crate::contexts::WithdrawStake;
+use crate::errors::VaultError;
+
+pub fn handler(ctx: Context<WithdrawStake>) -> Result<()> {
+    let vault = &ctx.accounts.vault;
+    let vault_key = vault.key();
+    let vault_bump = [ctx.bumps.vault];
+    let vault_seeds: &[&[u8]] = &[b"vault", vault.creator.as_ref(), vault.name.as_bytes(), &vault_bump];
+
+    // Withdraw the whole balance - principal, rewards and the rent-exempt reserve.
+    // The Stake program rejects this until the account is fully deactivated.
+    let lamports = ctx.accounts.stake_account.lamports();
+
+    invoke_signed(
+        &stake_instruction::withdraw(
+            &ctx.accounts.stake_account.key(),
+            &vault_key,
+            &vault_key,
+            lamports,
+            None,
+        ),
+        &[
+            ctx.accounts.stake_account.to_account_info(),
+            vault.to_account_info(),
+            ctx.accounts.clock.to_account_info(),
+            ctx.accounts.stake_history.to_account_info(),
+            vault.to_account_info(),
+        ],
+        &[vault_seeds],
+    )?;
+
+    // The vault_stake record is closed by its `close = signer` constraint
+    ctx.accounts.vault.staked_lamports = ctx.accounts.vault.staked_lamports
+        .checked_sub(ctx.accounts.vault_stake.lamports)
+        .ok_or(VaultError::InvalidAmount)?;
+
+    Ok(())
+}
@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+use crate::contexts::WithdrawTo;
+use crate::errors::VaultError;
+use crate::events::Withdrawn;
+use crate::instructions::withdraw::pay_out;
+use crate::state::{HistoryKind, VaultHistory};
+
+pub fn handler(ctx: Context<WithdrawTo>, amount: u64) -> Result<()> {
+    let clock = Clock::get()?;
+    require!(
+        clock.unix_timestamp >= ctx.accounts.allowed_destination.active_at,
+        VaultError::DestinationNotActive
+    );
+
+    let recipient_info = ctx.accounts.recipient.to_account_info();
+    pay_out(
+        &mut ctx.accounts.vault,
+        &recipient_info,
+        amount,
+        Some(&ctx.accounts.allowed_destination),
+    )?;
+
+    // Owner activity resets the dead-man's switch
+    ctx.accounts.vault.touch(clock.unix_timestamp);
+
+    VaultHistory::record(
+        &ctx.accounts.history,
+        HistoryKind::Withdrawal,
+        amount,
+        recipient_info.key(),
+        &ctx.accounts.vault.to_account_info(),
+    )?;
+
+    emit_cpi!(Withdrawn::new(
+        &ctx.accounts.vault,
+        ctx.accounts.signer.key(),
+        recipient_info.key(),
+        amount,
+        &clock,
+    ));
+
+    Ok(())
+}
@@ -0,0 +1,65 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{transfer_checked, TransferChecked};
+use crate::contexts::WithdrawToken;
+use crate::errors::VaultError;
+use crate::events::TokenWithdrawn;
+use crate::instructions::withdraw::check_destination;
+
+pub fn handler(ctx: Context<WithdrawToken>, amount: u64) -> Result<()> {
+    let clock = Clock::get()?;
+
+    // The lock covers the tokens as well; the withdrawal limit is in lamports and does not
+    require!(
+        !ctx.accounts.vault.lock.is_active(&clock),
+        VaultError::VaultLocked
+    );
+    // Token accounts are allowlisted by their owner's address
+    check_destination(
+        &ctx.accounts.vault,
+        &ctx.accounts.recipient_token.owner,
+        ctx.accounts.allowed_destination.as_ref(),
+        clock.unix_timestamp,
+    )?;
+
+    // The vault PDA owns its token accounts, so the transfer is signed with the vault seeds
+    let vault = &ctx.accounts.vault;
+    let vault_bump = [ctx.bumps.vault];
+    let vault_seeds: &[&[u8]] = &[b"vault", vault.creator.as_ref(), vault.name.as_bytes(), &vault_bump];
+
+    transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.vault_token.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                to: ctx.accounts.recipient_token.to_account_info(),
+                authority: ctx.accounts.vault.to_account_info(),
+            },
+            &[vault_seeds],
+        ),
+        amount,
+        ctx.accounts.mint.decimals,
+    )?;
+
+    let totals = &mut ctx.accounts.totals;
+    totals.total_withdrawn = totals.total_withdrawn
+        .checked_add(amount)
+        .ok_or(VaultError::InvalidAmount)?;
+
+    // Owner activity resets the dead-man's switch
+    ctx.accounts.vault.touch(clock.unix_timestamp);
+
+    emit_cpi!(TokenWithdrawn {
+        vault: ctx.accounts.vault.key(),
+        owner: ctx.accounts.vault.owner,
+        mint: ctx.accounts.totals.mint,
+        recipient: ctx.accounts.recipient_token.key(),
+        amount,
+        total_deposited: ctx.accounts.totals.total_deposited,
+        total_withdrawn: ctx.accounts.totals.total_withdrawn,
+        slot: clock.slot,
+        unix_timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -3,12 +3,14 @@ use anchor_lang::prelude::*;
 // Import modules
 pub mod state;
 pub mod errors;
+pub mod events;
 pub mod instructions;
 pub mod contexts; // Account structs (renamed from 'accounts' to avoid conflict with Anchor's generated module)
 
 // Re-export for convenience
 pub use state::*;
 pub use errors::*;
+pub use events::*;
 // Re-export account structs at crate root for #[program] macro
 pub use contexts::*;
 
@@ -33,8 +35,370 @@ pub mod my_first_anchor_program {
         instructions::withdraw::handler(ctx, amount)
     }
 
-    // Example: Read vault data using LazyAccount (read-only, efficient)
-    pub fn read_vault(ctx: Context<ReadVaultAction>) -> Result<()> {
+    // View function: returns a VaultSnapshot as return data, open to any caller
+    pub fn read_vault(ctx: Context<ReadVaultAction>) -> Result<VaultSnapshot> {
         instructions::read_vault::handler(ctx)
     }
+
+    // Lock withdrawals until a timestamp or slot (commitment the owner cannot undo)
+    pub fn lock_vault(ctx: Context<LockAction>, lock: VaultLock) -> Result<()> {
+        instructions::lock_vault::handler(ctx, lock)
+    }
+
+    // Push an active lock's unlock point later - never earlier
+    pub fn extend_lock(ctx: Context<LockAction>, lock: VaultLock) -> Result<()> {
+        instructions::extend_lock::handler(ctx, lock)
+    }
+
+    // Register a delegate key that can withdraw within spending caps until it expires
+    pub fn add_delegate(
+        ctx: Context<AddDelegate>,
+        delegate: Pubkey,
+        destination: Option<Pubkey>,
+        lifetime_cap: u64,
+        period_cap: u64,
+        period_seconds: i64,
+        expires_at: i64,
+    ) -> Result<()> {
+        instructions::add_delegate::handler(
+            ctx,
+            delegate,
+            destination,
+            lifetime_cap,
+            period_cap,
+            period_seconds,
+            expires_at,
+        )
+    }
+
+    // Revoke a delegate and reclaim the rent of its PDA
+    pub fn revoke_delegate(ctx: Context<RevokeDelegate>) -> Result<()> {
+        instructions::revoke_delegate::handler(ctx)
+    }
+
+    // Withdraw as a delegate - pays the owner or the delegate's configured destination
+    pub fn delegate_withdraw(ctx: Context<DelegateWithdraw>, amount: u64) -> Result<()> {
+        instructions::delegate_withdraw::handler(ctx, amount)
+    }
+
+    // Hand control of the vault to an M-of-N multisig
+    pub fn create_multisig(
+        ctx: Context<CreateMultisig>,
+        members: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        instructions::create_multisig::handler(ctx, members, threshold)
+    }
+
+    // Propose a withdrawal or config change (counts as the proposer's approval)
+    pub fn create_proposal(ctx: Context<CreateProposal>, action: MultisigAction) -> Result<()> {
+        instructions::create_proposal::handler(ctx, action)
+    }
+
+    // Approve a pending proposal as a multisig member
+    pub fn approve_proposal(ctx: Context<ApproveProposal>) -> Result<()> {
+        instructions::approve_proposal::handler(ctx)
+    }
+
+    // Execute a proposal once it has reached the threshold
+    pub fn execute_proposal(ctx: Context<ExecuteProposal>) -> Result<()> {
+        instructions::execute_proposal::handler(ctx)
+    }
+
+    // Drain the vault (rent included) to the owner or a chosen recipient and close it
+    pub fn close_vault(ctx: Context<CloseVault>) -> Result<()> {
+        instructions::close_vault::handler(ctx)
+    }
+
+    // Start a two-step ownership transfer (None cancels a pending transfer)
+    pub fn propose_owner(ctx: Context<ProposeOwner>, new_owner: Option<Pubkey>) -> Result<()> {
+        instructions::propose_owner::handler(ctx, new_owner)
+    }
+
+    // Complete an ownership transfer - must be signed by the proposed owner
+    pub fn accept_owner(ctx: Context<AcceptOwner>) -> Result<()> {
+        instructions::accept_owner::handler(ctx)
+    }
+
+    // Set, tighten, loosen or remove the per-window withdrawal limit
+    // Tightening applies immediately, loosening is timelocked
+    pub fn set_withdrawal_limit(
+        ctx: Context<WithdrawalLimitAction>,
+        limit: Option<WithdrawalLimit>,
+    ) -> Result<()> {
+        instructions::set_withdrawal_limit::handler(ctx, limit)
+    }
+
+    // Apply a queued withdrawal limit change once its timelock has passed
+    pub fn apply_withdrawal_limit(ctx: Context<WithdrawalLimitAction>) -> Result<()> {
+        instructions::apply_withdrawal_limit::handler(ctx)
+    }
+
+    // Create the index account that lists an owner's named vaults
+    pub fn initialize_owner_index(ctx: Context<InitializeOwnerIndex>) -> Result<()> {
+        instructions::initialize_owner_index::handler(ctx)
+    }
+
+    // Create an additional vault keyed by a short name (e.g. "rent", "payroll")
+    #[access_control(instructions::initialize_named::validate_name(&name))]
+    pub fn initialize_named(ctx: Context<InitializeNamedVault>, name: String) -> Result<()> {
+        instructions::initialize_named::handler(ctx, name)
+    }
+
+    // Fund any owner's vault - per-depositor totals are kept in a receipt PDA
+    pub fn deposit_to(ctx: Context<DepositTo>, amount: u64) -> Result<()> {
+        instructions::deposit_to::handler(ctx, amount)
+    }
+
+    // Stream lamports to a recipient at a fixed rate between start and end (optional cliff)
+    pub fn create_stream(
+        ctx: Context<CreateStream>,
+        rate_per_second: u64,
+        start: i64,
+        end: i64,
+        cliff: Option<i64>,
+    ) -> Result<()> {
+        instructions::create_stream::handler(ctx, rate_per_second, start, end, cliff)
+    }
+
+    // Recipient withdraws whatever has vested and not been claimed yet
+    pub fn claim_stream(ctx: Context<ClaimStream>) -> Result<()> {
+        instructions::claim_stream::handler(ctx)
+    }
+
+    // Owner cancels a stream - the vested part is paid out, the rest stays in the vault
+    pub fn cancel_stream(ctx: Context<CancelStream>) -> Result<()> {
+        instructions::cancel_stream::handler(ctx)
+    }
+
+    // Attach a zero-copy ring buffer recording the vault's recent operations
+    pub fn initialize_history(ctx: Context<InitializeHistory>) -> Result<()> {
+        instructions::initialize_history::handler(ctx)
+    }
+
+    // Move part of the vault balance into a new stake account delegated to a validator
+    pub fn delegate_stake(ctx: Context<DelegateStake>, stake_id: u64, amount: u64) -> Result<()> {
+        instructions::delegate_stake::handler(ctx, stake_id, amount)
+    }
+
+    // Start cooling down a vault stake account
+    pub fn deactivate_stake(ctx: Context<DeactivateStake>) -> Result<()> {
+        instructions::deactivate_stake::handler(ctx)
+    }
+
+    // Withdraw a deactivated stake account (principal + rewards) back into the vault
+    pub fn withdraw_stake(ctx: Context<WithdrawStake>) -> Result<()> {
+        instructions::withdraw_stake::handler(ctx)
+    }
+
+    // Register the guardians that can recover the vault, and how many must approve
+    pub fn set_guardians(ctx: Context<SetGuardians>, guardians: Vec<Pubkey>, threshold: u8) -> Result<()> {
+        instructions::set_guardians::handler(ctx, guardians, threshold)
+    }
+
+    // Remove the guardian set (and any recovery in progress)
+    pub fn remove_guardians(ctx: Context<RemoveGuardians>) -> Result<()> {
+        instructions::remove_guardians::handler(ctx)
+    }
+
+    // Guardian starts moving the vault to a new owner key
+    pub fn start_recovery(ctx: Context<GuardianAction>, new_owner: Pubkey) -> Result<()> {
+        instructions::start_recovery::handler(ctx, new_owner)
+    }
+
+    // Guardian approves the recovery in progress
+    pub fn approve_recovery(ctx: Context<GuardianAction>) -> Result<()> {
+        instructions::approve_recovery::handler(ctx)
+    }
+
+    // Owner vetoes the recovery in progress
+    pub fn cancel_recovery(ctx: Context<CancelRecovery>) -> Result<()> {
+        instructions::cancel_recovery::handler(ctx)
+    }
+
+    // Hand the vault to the recovered key once approved and past the challenge period
+    pub fn finish_recovery(ctx: Context<FinishRecovery>) -> Result<()> {
+        instructions::finish_recovery::handler(ctx)
+    }
+
+    // Set (or remove with None) the beneficiary who inherits the vault after `inactivity_period`
+    pub fn set_beneficiary(
+        ctx: Context<InheritanceAction>,
+        beneficiary: Option<Pubkey>,
+        inactivity_period: i64,
+    ) -> Result<()> {
+        instructions::set_beneficiary::handler(ctx, beneficiary, inactivity_period)
+    }
+
+    // Owner proves they are still active without moving funds
+    pub fn heartbeat(ctx: Context<InheritanceAction>) -> Result<()> {
+        instructions::heartbeat::handler(ctx)
+    }
+
+    // Beneficiary takes ownership of the vault after the owner went inactive
+    pub fn claim_inheritance(ctx: Context<ClaimInheritance>) -> Result<()> {
+        instructions::claim_inheritance::handler(ctx)
+    }
+
+    // Relayer submits a withdrawal the owner signed off-chain (verified by the Ed25519 program)
+    pub fn withdraw_signed(
+        ctx: Context<WithdrawSigned>,
+        authorization: WithdrawalAuthorization,
+    ) -> Result<()> {
+        instructions::withdraw_signed::handler(ctx, authorization)
+    }
+
+    // Grant a temporary session key scoped to deposits, or to capped withdrawals to one destination
+    pub fn create_session(
+        ctx: Context<CreateSession>,
+        session_key: Pubkey,
+        scope: SessionScope,
+        expires_at: i64,
+    ) -> Result<()> {
+        instructions::create_session::handler(ctx, session_key, scope, expires_at)
+    }
+
+    // Close a session - the owner at any time, anyone once it has expired
+    pub fn revoke_session(ctx: Context<RevokeSession>) -> Result<()> {
+        instructions::revoke_session::handler(ctx)
+    }
+
+    // Deposit signed by a DepositOnly session key
+    pub fn session_deposit(ctx: Context<SessionDeposit>, amount: u64) -> Result<()> {
+        instructions::session_deposit::handler(ctx, amount)
+    }
+
+    // Withdrawal signed by a Withdraw session key, within its cap and to its destination
+    pub fn session_withdraw(ctx: Context<SessionWithdraw>, amount: u64) -> Result<()> {
+        instructions::session_withdraw::handler(ctx, amount)
+    }
+
+    // Upgrade a vault created under an older layout: realloc, top up rent, fill defaults
+    pub fn migrate_vault(ctx: Context<MigrateVault>) -> Result<()> {
+        instructions::migrate_vault::handler(ctx)
+    }
+
+    // Turn an empty vault into a shared pot where depositors hold shares
+    pub fn enable_pooling(ctx: Context<EnablePooling>) -> Result<()> {
+        instructions::enable_pooling::handler(ctx)
+    }
+
+    // Deposit into a pooled vault and receive shares at the current lamport-per-share rate
+    pub fn pool_deposit(ctx: Context<PoolDeposit>, amount: u64) -> Result<()> {
+        instructions::pool_deposit::handler(ctx, amount)
+    }
+
+    // Burn shares of a pooled vault for their current lamport value
+    pub fn pool_withdraw(ctx: Context<PoolWithdraw>, shares: u64) -> Result<()> {
+        instructions::pool_withdraw::handler(ctx, shares)
+    }
+
+    // Allowlist a withdrawal destination - usable after DESTINATION_DELAY_SECONDS
+    pub fn add_destination(ctx: Context<AddDestination>, destination: Pubkey) -> Result<()> {
+        instructions::add_destination::handler(ctx, destination)
+    }
+
+    // Remove a destination from the allowlist (takes effect immediately)
+    pub fn remove_destination(ctx: Context<RemoveDestination>) -> Result<()> {
+        instructions::remove_destination::handler(ctx)
+    }
+
+    // Owner withdraws to an allowlisted destination
+    pub fn withdraw_to(ctx: Context<WithdrawTo>, amount: u64) -> Result<()> {
+        instructions::withdraw_to::handler(ctx, amount)
+    }
+
+    // Set up an (inactive) governance for the vault: quorum, voting period and execution delay
+    pub fn create_governance(
+        ctx: Context<CreateGovernance>,
+        quorum_weight: u64,
+        voting_period: i64,
+        execution_delay: i64,
+    ) -> Result<()> {
+        instructions::create_governance::handler(ctx, quorum_weight, voting_period, execution_delay)
+    }
+
+    // Owner adds a voting member while the governance is inactive
+    pub fn add_governance_member(
+        ctx: Context<AddGovernanceMember>,
+        member: Pubkey,
+        weight: u64,
+    ) -> Result<()> {
+        instructions::add_governance_member::handler(ctx, member, weight)
+    }
+
+    // Hand control of the vault to its governance (cannot be undone)
+    pub fn activate_governance(ctx: Context<ActivateGovernance>) -> Result<()> {
+        instructions::activate_governance::handler(ctx)
+    }
+
+    // Anyone opens a spend proposal, which is open for votes for the voting period
+    pub fn create_spend_proposal(
+        ctx: Context<CreateSpendProposal>,
+        recipient: Pubkey,
+        amount: u64,
+        memo: String,
+    ) -> Result<()> {
+        instructions::create_spend_proposal::handler(ctx, recipient, amount, memo)
+    }
+
+    // Member votes for or against a proposal with their full weight
+    pub fn cast_vote(ctx: Context<CastVote>, approve: bool) -> Result<()> {
+        instructions::cast_vote::handler(ctx, approve)
+    }
+
+    // Pay out a passed proposal once voting and the execution delay are over
+    pub fn execute_spend_proposal(ctx: Context<ExecuteSpendProposal>) -> Result<()> {
+        instructions::execute_spend_proposal::handler(ctx)
+    }
+
+    // Owner lets a merchant pull a fixed amount once per period, optionally until an end time
+    pub fn create_subscription(
+        ctx: Context<CreateSubscription>,
+        amount_per_period: u64,
+        period_seconds: i64,
+        ends_at: Option<i64>,
+    ) -> Result<()> {
+        instructions::create_subscription::handler(ctx, amount_per_period, period_seconds, ends_at)
+    }
+
+    // Merchant collects one period's amount once that period has elapsed
+    pub fn collect_subscription(ctx: Context<CollectSubscription>) -> Result<()> {
+        instructions::collect_subscription::handler(ctx)
+    }
+
+    // Owner cancels a subscription and gets its rent back
+    pub fn cancel_subscription(ctx: Context<CancelSubscription>) -> Result<()> {
+        instructions::cancel_subscription::handler(ctx)
+    }
+
+    // Owner moves lamports straight into another vault, without a withdraw-then-deposit round trip
+    pub fn transfer_between_vaults(ctx: Context<TransferBetweenVaults>, amount: u64) -> Result<()> {
+        instructions::transfer_between_vaults::handler(ctx, amount)
+    }
+
+    // Owner deposits SPL / Token-2022 tokens into the vault's token account for the mint
+    pub fn deposit_token(ctx: Context<DepositToken>, amount: u64) -> Result<()> {
+        instructions::deposit_token::handler(ctx, amount)
+    }
+
+    // Owner withdraws tokens from the vault's token account for the mint
+    pub fn withdraw_token(ctx: Context<WithdrawToken>, amount: u64) -> Result<()> {
+        instructions::withdraw_token::handler(ctx, amount)
+    }
+
+    // Turn the destination allowlist on (at once) or off (after the timelock) for every payout
+    pub fn set_allowlist_enforced(ctx: Context<AllowlistAction>, enforced: bool) -> Result<()> {
+        instructions::set_allowlist_enforced::handler(ctx, enforced)
+    }
+
+    // Apply a queued allowlist disable once its timelock has passed
+    pub fn apply_allowlist_change(ctx: Context<AllowlistAction>) -> Result<()> {
+        instructions::apply_allowlist_change::handler(ctx)
+    }
+
+    // Owner closes an emptied token account of the vault together with its totals
+    pub fn close_token_account(ctx: Context<CloseTokenAccount>) -> Result<()> {
+        instructions::close_token_account::handler(ctx)
+    }
 }
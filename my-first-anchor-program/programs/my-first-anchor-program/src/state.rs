@@ -1,10 +1,909 @@
 use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+use crate::errors::VaultError;
 
 // Custom vault account structure to store metadata
+// Layout changes must bump VAULT_VERSION, give the new layout its own discriminator and
+// teach migrate_vault to upgrade the previous layout. With a new discriminator, accounts
+// still on an old layout fail Account<Vault> checks until migrated, instead of being
+// deserialized as garbage
 #[derive(InitSpace)]
-#[account]
+#[account(discriminator = &VAULT_V1_DISCRIMINATOR)]
 pub struct Vault {
+    // Layout version - always the first field, so migrate_vault can read it from any layout
+    pub version: u8,
+    pub owner: Pubkey,
+    pub total_deposits: u64,
+    pub total_withdrawals: u64,
+    pub lock: VaultLock,
+    // Set once a VaultMultisig or an active Governance controls the vault -
+    // owner-only paths are then disabled
+    pub multisig: bool,
+    // Open delegate, stream, subscription, guardian set, session and allowlist destination PDAs
+    // tied to the vault - must be zero before the vault can be closed, otherwise they would
+    // come back to life if the same PDA is re-initialized
+    pub grant_count: u32,
+    // Key the PDA is derived from (the initializer) - stays fixed when ownership moves
+    pub creator: Pubkey,
+    // Set by propose_owner, cleared by accept_owner
+    pub pending_owner: Option<Pubkey>,
+    // Optional cap on outflows per window, enforced for every payout
+    pub withdrawal_limit: Option<WithdrawalLimit>,
+    // Loosening the limit is queued here and only applies after LIMIT_CHANGE_DELAY_SECONDS
+    pub pending_limit_change: Option<PendingLimitChange>,
+    // Current bucket of the withdrawal window
+    pub window_start: i64,
+    pub window_withdrawn: u64,
+    // Last PDA seed - empty for the default vault created by `initialize`
+    #[max_len(MAX_VAULT_NAME_LEN)]
+    pub name: String,
+    // Lamports moved into stake accounts (principal) - the vault balance is the liquid part
+    pub staked_lamports: u64,
+    // Dead-man's switch: once `inactivity_period` passes without an owner action,
+    // the beneficiary can claim ownership of the vault
+    pub beneficiary: Option<Pubkey>,
+    pub inactivity_period: i64,
+    // Refreshed by owner deposits, withdrawals and heartbeats
+    pub last_active_at: i64,
+    // Replay protection for signed withdrawals: nonces below `nonce_base` are spent,
+    // and bit i of `nonce_bitmap` marks nonce_base + i as spent
+    pub nonce_base: u64,
+    pub nonce_bitmap: [u64; 4],
+    // Shared-vault mode: depositors hold shares of the pot and the owner cannot pay out
+    pub pooled: bool,
+    pub total_shares: u64,
+    // While set, every payout must go to an active AllowedDestination. Turning it on applies
+    // at once; turning it off is queued in `allowlist_disable_at` like loosening the limit
+    pub allowlist_enforced: bool,
+    pub allowlist_disable_at: Option<i64>,
+    // Set by initialize_history - recording instructions then require the history account,
+    // so the ring buffer cannot silently miss operations
+    pub history_attached: bool,
+    // Open VaultTokenTotals PDAs (one per mint deposited through deposit_token) - must be zero
+    // before the vault can be closed or handed over, or the tokens would be stranded
+    pub token_positions: u32,
+    // Set when initialize mints an ownership token. Grants bound to a key (delegates, streams,
+    // guardians, ...) are then refused, since they would stay with the seller of the token
+    pub tokenized: bool,
+}
+
+// Current Vault layout version
+pub const VAULT_VERSION: u8 = 1;
+
+// sha256("account:VaultV1")[..8]
+pub const VAULT_V1_DISCRIMINATOR: [u8; 8] = [27, 177, 104, 68, 34, 4, 147, 92];
+
+// sha256("account:Vault")[..8] - Anchor's default, used by the unversioned version 0
+pub const VAULT_V0_DISCRIMINATOR: [u8; 8] = [211, 8, 232, 43, 2, 152, 117, 119];
+
+// The original unversioned layout (version 0): owner and totals only
+// Its PDA was [b"vault", owner], which is the default vault address for creator = owner
+#[derive(AnchorDeserialize)]
+#[cfg_attr(test, derive(AnchorSerialize))]
+pub struct VaultV0 {
+    pub owner: Pubkey,
+    pub total_deposits: u64,
+    pub total_withdrawals: u64,
+}
+
+impl VaultV0 {
+    // Discriminator + owner + totals
+    pub const LEN: usize = 8 + 32 + 8 + 8;
+}
+
+// A PDA seed can be at most 32 bytes
+pub const MAX_VAULT_NAME_LEN: usize = 32;
+
+// Number of nonces tracked by the vault's nonce bitmap
+pub const NONCE_WINDOW: u64 = 256;
+
+// Delay before a looser withdrawal limit (or removing it) takes effect
+pub const LIMIT_CHANGE_DELAY_SECONDS: i64 = 24 * 60 * 60;
+
+// Virtual shares and lamports added to both sides of the share price, so the first
+// depositor cannot inflate the price (by donating to the vault) and round later deposits down
+pub const POOL_VIRTUAL_SHARES: u64 = 1_000;
+pub const POOL_VIRTUAL_LAMPORTS: u64 = 1_000;
+
+impl Vault {
+    // Fresh vault state - `creator` is both the first owner and the PDA seed key
+    pub fn new(creator: Pubkey, name: String) -> Self {
+        Self {
+            version: VAULT_VERSION,
+            owner: creator,
+            total_deposits: 0,
+            total_withdrawals: 0,
+            lock: VaultLock::None,
+            multisig: false,
+            grant_count: 0,
+            creator,
+            pending_owner: None,
+            withdrawal_limit: None,
+            pending_limit_change: None,
+            window_start: 0,
+            window_withdrawn: 0,
+            name,
+            staked_lamports: 0,
+            beneficiary: None,
+            inactivity_period: 0,
+            last_active_at: 0,
+            nonce_base: 0,
+            nonce_bitmap: [0; 4],
+            pooled: false,
+            total_shares: 0,
+            allowlist_enforced: false,
+            allowlist_disable_at: None,
+            history_attached: false,
+            token_positions: 0,
+            tokenized: false,
+        }
+    }
+
+    // Lamports that back the pool's shares: the balance above the rent floor, so donations
+    // accrue to all shares pro rata. Pooled vaults cannot stake, but staked principal still
+    // counts so that enable_pooling sees a vault with stake outstanding as non-empty
+    pub fn pool_value(&self, balance: u64) -> Result<u64> {
+        balance
+            .saturating_sub(Vault::rent_floor()?)
+            .checked_add(self.staked_lamports)
+            .ok_or(VaultError::InvalidAmount.into())
+    }
+
+    // Shares minted for a deposit of `amount` into a pool worth `pool_value`
+    // Rounded down, in favour of existing holders
+    pub fn shares_for_deposit(&self, amount: u64, pool_value: u64) -> Result<u64> {
+        let shares = amount as u128 * (self.total_shares as u128 + POOL_VIRTUAL_SHARES as u128)
+            / (pool_value as u128 + POOL_VIRTUAL_LAMPORTS as u128);
+        u64::try_from(shares).map_err(|_| VaultError::InvalidAmount.into())
+    }
+
+    // Lamports paid out for redeeming `shares` from a pool worth `pool_value`
+    // Rounded down, in favour of the pool
+    pub fn lamports_for_shares(&self, shares: u64, pool_value: u64) -> Result<u64> {
+        let lamports = shares as u128 * (pool_value as u128 + POOL_VIRTUAL_LAMPORTS as u128)
+            / (self.total_shares as u128 + POOL_VIRTUAL_SHARES as u128);
+        u64::try_from(lamports).map_err(|_| VaultError::InvalidAmount.into())
+    }
+
+    // Mark a signed-withdrawal nonce as spent - fails if it was spent before
+    // Nonces can be used in any order within the window; a nonce past the window slides
+    // it forward, and the nonces that fall out of it count as spent
+    pub fn use_nonce(&mut self, nonce: u64) -> Result<()> {
+        require!(nonce >= self.nonce_base, VaultError::NonceAlreadyUsed);
+
+        let window_end = self.nonce_base.saturating_add(NONCE_WINDOW);
+        if nonce >= window_end {
+            let shift = nonce - window_end + 1;
+            let mut bitmap = [0u64; 4];
+            for bit in shift..NONCE_WINDOW {
+                if nonce_bit(&self.nonce_bitmap, bit) {
+                    set_nonce_bit(&mut bitmap, bit - shift);
+                }
+            }
+            self.nonce_bitmap = bitmap;
+            self.nonce_base += shift;
+        }
+
+        let bit = nonce - self.nonce_base;
+        require!(!nonce_bit(&self.nonce_bitmap, bit), VaultError::NonceAlreadyUsed);
+        set_nonce_bit(&mut self.nonce_bitmap, bit);
+
+        Ok(())
+    }
+
+    // The owner key itself, or - for a tokenized vault, whose owner is its ownership mint -
+    // whoever holds the mint's only token
+    pub fn is_owner(&self, signer: &Pubkey, ownership_token: Option<&Account<TokenAccount>>) -> bool {
+        *signer == self.owner
+            || ownership_token.is_some_and(|token| {
+                token.mint == self.owner && token.owner == *signer && token.amount == 1
+            })
+    }
+
+    // Lamports that must stay in the vault account to keep it rent-exempt
+    pub fn rent_floor() -> Result<u64> {
+        Ok(Rent::get()?.minimum_balance(Vault::INIT_SPACE + Vault::DISCRIMINATOR.len()))
+    }
+
+    // Record that the owner is still around (resets the dead-man's switch)
+    pub fn touch(&mut self, now: i64) {
+        self.last_active_at = now;
+    }
+
+    // True once the owner has been inactive for the whole period and a beneficiary is set
+    pub fn is_inheritable(&self, now: i64) -> bool {
+        self.beneficiary.is_some()
+            && now >= self.last_active_at.saturating_add(self.inactivity_period)
+    }
+
+    // Count an outflow against the withdrawal limit (no-op when no limit is set)
+    // The window is bucketed: it starts at the first outflow after the previous bucket expired
+    pub fn consume_withdrawal_allowance(&mut self, amount: u64, now: i64) -> Result<()> {
+        let Some(limit) = self.withdrawal_limit else {
+            return Ok(());
+        };
+
+        if now.saturating_sub(self.window_start) >= limit.window_seconds {
+            self.window_start = now;
+            self.window_withdrawn = 0;
+        }
+
+        let window_withdrawn = self.window_withdrawn
+            .checked_add(amount)
+            .ok_or(VaultError::InvalidAmount)?;
+        require!(
+            window_withdrawn <= limit.max_lamports,
+            VaultError::WithdrawalLimitExceeded
+        );
+        self.window_withdrawn = window_withdrawn;
+
+        Ok(())
+    }
+}
+
+fn nonce_bit(bitmap: &[u64; 4], bit: u64) -> bool {
+    bitmap[(bit / 64) as usize] & (1 << (bit % 64)) != 0
+}
+
+fn set_nonce_bit(bitmap: &mut [u64; 4], bit: u64) {
+    bitmap[(bit / 64) as usize] |= 1 << (bit % 64);
+}
+
+// Withdrawal the owner signs off-chain for a relayer to submit with withdraw_signed
+// The signed message is the Borsh encoding of this struct
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct WithdrawalAuthorization {
+    pub vault: Pubkey,
+    pub amount: u64,
+    pub recipient: Pubkey,
+    pub nonce: u64,
+    pub expires_at: i64,
+}
+
+// Programs and sysvars withdraw_signed reads to verify the owner's signature
+pub const ED25519_PROGRAM_ID: Pubkey = pubkey!("Ed25519SigVerify111111111111111111111111111");
+pub const INSTRUCTIONS_SYSVAR_ID: Pubkey = pubkey!("Sysvar1nstructions1111111111111111111111111");
+
+// Returned by read_vault - a point-in-time view of a vault's balances and totals
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct VaultSnapshot {
+    pub vault: Pubkey,
     pub owner: Pubkey,
+    // Lamports held by the vault account, rent floor included
+    pub balance: u64,
+    pub staked_lamports: u64,
+    pub rent_floor: u64,
+    // What the owner can withdraw now, after the rent floor, lock and withdrawal limit
+    pub withdrawable: u64,
     pub total_deposits: u64,
     pub total_withdrawals: u64,
 }
+
+// At most `max_lamports` can leave the vault per `window_seconds`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub struct WithdrawalLimit {
+    pub max_lamports: u64,
+    pub window_seconds: i64,
+}
+
+impl WithdrawalLimit {
+    // A stricter limit allows at most as much over at least as long a window
+    pub fn is_at_least_as_strict_as(&self, other: &WithdrawalLimit) -> bool {
+        self.max_lamports <= other.max_lamports && self.window_seconds >= other.window_seconds
+    }
+
+    // Lamports still allowed in the window starting at `window_start` - the full cap once it expired
+    pub fn remaining(&self, window_start: i64, window_withdrawn: u64, now: i64) -> u64 {
+        if now.saturating_sub(window_start) >= self.window_seconds {
+            self.max_lamports
+        } else {
+            self.max_lamports.saturating_sub(window_withdrawn)
+        }
+    }
+}
+
+// A queued withdrawal limit change - `limit: None` removes the limit
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub struct PendingLimitChange {
+    pub limit: Option<WithdrawalLimit>,
+    pub effective_at: i64,
+}
+
+// Withdrawal lock - the vault stays locked until the clock passes the given point
+// Struct variants (instead of tuple variants) keep the IDL/TS representation readable
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum VaultLock {
+    None,
+    UntilTimestamp { unix_timestamp: i64 },
+    UntilSlot { slot: u64 },
+}
+
+impl VaultLock {
+    // A lock is active while the clock has not yet reached the unlock point
+    pub fn is_active(&self, clock: &Clock) -> bool {
+        match *self {
+            VaultLock::None => false,
+            VaultLock::UntilTimestamp { unix_timestamp } => clock.unix_timestamp < unix_timestamp,
+            VaultLock::UntilSlot { slot } => clock.slot < slot,
+        }
+    }
+
+    // Returns true if `other` is the same kind of lock and unlocks strictly later
+    pub fn is_extended_by(&self, other: &VaultLock) -> bool {
+        match (*self, *other) {
+            (
+                VaultLock::UntilTimestamp { unix_timestamp: current },
+                VaultLock::UntilTimestamp { unix_timestamp: new },
+            ) => new > current,
+            (VaultLock::UntilSlot { slot: current }, VaultLock::UntilSlot { slot: new }) => new > current,
+            _ => false,
+        }
+    }
+}
+
+// A key the owner allows to withdraw on their behalf, within spending caps
+// One PDA per (vault, delegate) pair: seeds = [b"delegate", vault, delegate]
+#[derive(InitSpace)]
+#[account]
+pub struct VaultDelegate {
+    pub vault: Pubkey,
+    pub delegate: Pubkey,
+    // Where delegate withdrawals are paid - None means the vault owner
+    pub destination: Option<Pubkey>,
+    pub lifetime_cap: u64,
+    pub total_spent: u64,
+    pub period_cap: u64,
+    pub period_seconds: i64,
+    pub period_start: i64,
+    pub period_spent: u64,
+    pub expires_at: i64,
+}
+
+impl VaultDelegate {
+    // Record a delegate withdrawal against both caps
+    // The period is bucketed: once it elapses, the bucket moves forward and spending resets
+    pub fn record_spend(&mut self, amount: u64, now: i64) -> Result<()> {
+        let elapsed = now.saturating_sub(self.period_start);
+        if elapsed >= self.period_seconds {
+            self.period_start = now - elapsed % self.period_seconds;
+            self.period_spent = 0;
+        }
+
+        let period_spent = self.period_spent
+            .checked_add(amount)
+            .ok_or(VaultError::InvalidAmount)?;
+        let total_spent = self.total_spent
+            .checked_add(amount)
+            .ok_or(VaultError::InvalidAmount)?;
+
+        require!(period_spent <= self.period_cap, VaultError::DelegateCapExceeded);
+        require!(total_spent <= self.lifetime_cap, VaultError::DelegateCapExceeded);
+
+        self.period_spent = period_spent;
+        self.total_spent = total_spent;
+
+        Ok(())
+    }
+}
+
+// What a session key may do - struct variants keep the IDL/TS representation readable
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum SessionScope {
+    // Deposit from the session key's own balance
+    DepositOnly,
+    // Withdraw up to `cap` lamports in total, only to `destination`
+    Withdraw { destination: Pubkey, cap: u64 },
+}
+
+// A short-lived key the owner lets act on the vault without signing each action:
+// seeds = [b"session", vault, session_key]
+// Unusable from `expires_at` on, after which anyone can close it and refund the owner
+#[derive(InitSpace)]
+#[account]
+pub struct VaultSession {
+    pub vault: Pubkey,
+    pub session_key: Pubkey,
+    pub scope: SessionScope,
+    // Lamports withdrawn so far (Withdraw scope)
+    pub withdrawn: u64,
+    pub expires_at: i64,
+}
+
+// Maximum number of members a vault multisig can hold
+pub const MAX_MULTISIG_MEMBERS: usize = 10;
+
+// M-of-N configuration controlling a vault: seeds = [b"multisig", vault]
+#[derive(InitSpace)]
+#[account]
+pub struct VaultMultisig {
+    pub vault: Pubkey,
+    #[max_len(MAX_MULTISIG_MEMBERS)]
+    pub members: Vec<Pubkey>,
+    pub threshold: u8,
+    // Index of the next proposal, used in the proposal PDA seeds
+    pub proposal_count: u64,
+}
+
+impl VaultMultisig {
+    pub fn is_member(&self, key: &Pubkey) -> bool {
+        self.members.contains(key)
+    }
+
+    // Threshold must be reachable and members must be unique
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            !self.members.is_empty() && self.members.len() <= MAX_MULTISIG_MEMBERS,
+            VaultError::InvalidMultisigConfig
+        );
+        require!(
+            self.threshold > 0 && self.threshold as usize <= self.members.len(),
+            VaultError::InvalidMultisigConfig
+        );
+        for (i, member) in self.members.iter().enumerate() {
+            require!(
+                !self.members[i + 1..].contains(member),
+                VaultError::InvalidMultisigConfig
+            );
+        }
+        Ok(())
+    }
+}
+
+// What a multisig proposal does once it has enough approvals
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum MultisigAction {
+    Withdraw { recipient: Pubkey, amount: u64 },
+    AddMember { member: Pubkey },
+    RemoveMember { member: Pubkey },
+    ChangeThreshold { threshold: u8 },
+}
+
+// A pending multisig action: seeds = [b"proposal", multisig, index]
+#[derive(InitSpace)]
+#[account]
+pub struct MultisigProposal {
+    pub multisig: Pubkey,
+    pub index: u64,
+    pub proposer: Pubkey,
+    pub action: MultisigAction,
+    // Approvals are checked against the current member list at execution time,
+    // so approvals from removed members stop counting
+    #[max_len(MAX_MULTISIG_MEMBERS)]
+    pub approvals: Vec<Pubkey>,
+    pub executed: bool,
+}
+
+// Maximum memo length of a spend proposal
+pub const MAX_MEMO_LEN: usize = 100;
+
+// Token-less DAO controlling a vault: seeds = [b"governance", vault]
+// The owner adds members while it is inactive; activating it hands over the vault for good
+#[derive(InitSpace)]
+#[account]
+pub struct Governance {
+    pub vault: Pubkey,
+    // A proposal passes with more yes than no weight and at least this much yes weight
+    pub quorum_weight: u64,
+    pub voting_period: i64,
+    // Wait between the end of voting and execution
+    pub execution_delay: i64,
+    pub total_weight: u64,
+    // Membership is fixed once active
+    pub active: bool,
+    // Index of the next proposal, used in the proposal PDA seeds
+    pub proposal_count: u64,
+}
+
+// A member's voting weight: seeds = [b"member", governance, member]
+#[derive(InitSpace)]
+#[account]
+pub struct GovernanceMember {
+    pub governance: Pubkey,
+    pub member: Pubkey,
+    pub weight: u64,
+}
+
+// A proposal to pay `amount` to `recipient`: seeds = [b"spend_proposal", governance, index]
+#[derive(InitSpace)]
+#[account]
+pub struct SpendProposal {
+    pub governance: Pubkey,
+    pub index: u64,
+    pub proposer: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    #[max_len(MAX_MEMO_LEN)]
+    pub memo: String,
+    pub voting_ends_at: i64,
+    pub yes_weight: u64,
+    pub no_weight: u64,
+    pub executed: bool,
+}
+
+impl SpendProposal {
+    pub fn passed(&self, quorum_weight: u64) -> bool {
+        self.yes_weight > self.no_weight && self.yes_weight >= quorum_weight
+    }
+}
+
+// One member's vote on a proposal: seeds = [b"vote", proposal, member]
+// Its existence is what stops a member from voting twice
+#[derive(InitSpace)]
+#[account]
+pub struct VoteRecord {
+    pub proposal: Pubkey,
+    pub member: Pubkey,
+    pub approve: bool,
+    pub weight: u64,
+}
+
+// Maximum number of vaults an owner index can list
+pub const MAX_INDEXED_VAULTS: usize = 16;
+
+// Lists the vaults created by `owner` so clients can enumerate them without
+// getProgramAccounts: seeds = [b"owner_index", owner]
+// Entries follow the creator, so a vault stays listed here after an ownership transfer
+#[derive(InitSpace)]
+#[account]
+pub struct OwnerIndex {
+    pub owner: Pubkey,
+    #[max_len(MAX_INDEXED_VAULTS)]
+    pub vaults: Vec<Pubkey>,
+}
+
+// A depositor's stake in a pooled vault: seeds = [b"shares", vault, depositor]
+// Created on the first pool_deposit - see Vault::shares_for_deposit for the share price
+#[derive(InitSpace)]
+#[account]
+pub struct PoolShares {
+    pub vault: Pubkey,
+    pub depositor: Pubkey,
+    pub shares: u64,
+}
+
+// Delay before a newly allowlisted destination can receive withdrawals
+pub const DESTINATION_DELAY_SECONDS: i64 = 24 * 60 * 60;
+
+// An address withdraw_to may pay - and, while the vault enforces its allowlist, the only
+// addresses any payout may go to: seeds = [b"destination", vault, destination]
+// Usable from `active_at` on, so a leaked owner key cannot add and drain to a new address at once
+#[derive(InitSpace)]
+#[account]
+pub struct AllowedDestination {
+    pub vault: Pubkey,
+    pub destination: Pubkey,
+    pub active_at: i64,
+}
+
+// Running total of one depositor's payments into a vault: seeds = [b"receipt", vault, depositor]
+// Created on the first deposit_to and updated on every later one
+#[derive(InitSpace)]
+#[account]
+pub struct DepositorReceipt {
+    pub vault: Pubkey,
+    pub depositor: Pubkey,
+    pub total_deposited: u64,
+    pub deposit_count: u64,
+    pub last_deposit_at: i64,
+}
+
+// Lamports streamed from a vault to a recipient: seeds = [b"stream", vault, recipient]
+// Nothing is reserved up front - claims are paid from the vault balance like any withdrawal
+// Only the owner can cancel a stream, so an open one blocks multisig and governance handovers
+#[derive(InitSpace)]
+#[account]
+pub struct Stream {
+    pub vault: Pubkey,
+    pub recipient: Pubkey,
+    pub rate_per_second: u64,
+    pub start: i64,
+    pub end: i64,
+    // Nothing can be claimed before the cliff, then everything vested since `start` unlocks
+    pub cliff: Option<i64>,
+    pub claimed: u64,
+}
+
+impl Stream {
+    // Lamports vested at `now` - linear between start and end, zero before the cliff
+    pub fn vested(&self, now: i64) -> Result<u64> {
+        if now < self.cliff.unwrap_or(self.start) || now <= self.start {
+            return Ok(0);
+        }
+
+        let elapsed = now.min(self.end) - self.start;
+        self.rate_per_second
+            .checked_mul(elapsed as u64)
+            .ok_or(VaultError::InvalidAmount.into())
+    }
+
+    // Vested but not yet claimed
+    pub fn claimable(&self, now: i64) -> Result<u64> {
+        Ok(self.vested(now)?.saturating_sub(self.claimed))
+    }
+}
+
+// Recurring pull payment a merchant collects from a vault: seeds = [b"subscription", vault, merchant]
+// Like a stream nothing is reserved - each period is paid from the vault balance when collected
+#[derive(InitSpace)]
+#[account]
+pub struct Subscription {
+    pub vault: Pubkey,
+    pub merchant: Pubkey,
+    pub amount_per_period: u64,
+    pub period_seconds: i64,
+    // End of the next uncollected period - it is paid in arrears, once this time has passed
+    pub next_collection_at: i64,
+    // Periods ending after this time are not covered
+    pub ends_at: Option<i64>,
+}
+
+// Running totals for one SPL / Token-2022 mint held by a vault: seeds = [b"vault_token", vault, mint]
+// The tokens themselves sit in the vault PDA's associated token account for the mint
+// Counted in `Vault::token_positions` until close_token_account closes both
+#[derive(InitSpace)]
+#[account]
+pub struct VaultTokenTotals {
+    pub vault: Pubkey,
+    pub mint: Pubkey,
+    pub total_deposited: u64,
+    pub total_withdrawn: u64,
+}
+
+// Number of operations kept in a vault's history ring buffer
+pub const HISTORY_LEN: usize = 32;
+
+#[repr(u8)]
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum HistoryKind {
+    Deposit = 0,
+    Withdrawal = 1,
+}
+
+// One recorded operation - fields are ordered so the struct has no implicit padding
+#[zero_copy]
+pub struct HistoryEntry {
+    pub counterparty: Pubkey,
+    pub amount: u64,
+    pub slot: u64,
+    pub balance_after: u64,
+    // HistoryKind as u8 (zero-copy accounts cannot hold Rust enums)
+    pub kind: u8,
+    pub _padding: [u8; 7],
+}
+
+// Fixed-size ring buffer of the last HISTORY_LEN operations: seeds = [b"history", vault]
+// Zero-copy, so appending does not deserialize the whole buffer
+#[account(zero_copy)]
+pub struct VaultHistory {
+    pub vault: Pubkey,
+    // Index the next entry is written to
+    pub head: u64,
+    // Total entries ever written - entries[..min(count, HISTORY_LEN)] are valid
+    pub count: u64,
+    pub entries: [HistoryEntry; HISTORY_LEN],
+}
+
+impl VaultHistory {
+    pub const INIT_SPACE: usize = std::mem::size_of::<VaultHistory>();
+
+    pub fn push(&mut self, entry: HistoryEntry) {
+        self.entries[self.head as usize] = entry;
+        self.head = (self.head + 1) % HISTORY_LEN as u64;
+        self.count = self.count.saturating_add(1);
+    }
+
+    // Append to the history if the caller passed it (vaults without history skip this)
+    pub fn record(
+        history: &Option<AccountLoader<VaultHistory>>,
+        kind: HistoryKind,
+        amount: u64,
+        counterparty: Pubkey,
+        vault_info: &AccountInfo,
+    ) -> Result<()> {
+        let Some(history) = history else {
+            return Ok(());
+        };
+
+        history.load_mut()?.push(HistoryEntry {
+            counterparty,
+            amount,
+            slot: Clock::get()?.slot,
+            balance_after: vault_info.lamports(),
+            kind: kind as u8,
+            _padding: [0; 7],
+        });
+
+        Ok(())
+    }
+}
+
+// Accounts the native Stake program expects alongside its instructions
+pub const STAKE_HISTORY_SYSVAR_ID: Pubkey = pubkey!("SysvarStakeHistory1111111111111111111111111");
+pub const STAKE_CONFIG_ID: Pubkey = pubkey!("StakeConfig11111111111111111111111111111111");
+
+// A stake account created and controlled by a vault: seeds = [b"vault_stake", stake_account]
+// The stake account itself is a PDA of this program: seeds = [b"stake", vault, stake_id]
+// and has the vault PDA as both staker and withdrawer
+#[derive(InitSpace)]
+#[account]
+pub struct VaultStake {
+    pub vault: Pubkey,
+    pub stake_account: Pubkey,
+    pub stake_id: u64,
+    pub vote_account: Pubkey,
+    // Principal moved out of the vault - rewards come back on withdraw_stake
+    pub lamports: u64,
+}
+
+// Maximum number of guardians a vault can register
+pub const MAX_GUARDIANS: usize = 10;
+
+// Time the owner has to veto a recovery before it can be finished
+pub const RECOVERY_CHALLENGE_SECONDS: i64 = 3 * 24 * 60 * 60;
+
+// Keys that can jointly move a vault to a new owner if the owner key is lost:
+// seeds = [b"guardians", vault]
+#[derive(InitSpace)]
+#[account]
+pub struct GuardianSet {
+    pub vault: Pubkey,
+    #[max_len(MAX_GUARDIANS)]
+    pub guardians: Vec<Pubkey>,
+    pub threshold: u8,
+    // At most one recovery runs at a time
+    pub recovery: Option<Recovery>,
+}
+
+// A recovery started by a guardian - the owner can veto it until it is finished
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
+pub struct Recovery {
+    pub new_owner: Pubkey,
+    // Checked against the current guardian list when the recovery is finished
+    #[max_len(MAX_GUARDIANS)]
+    pub approvals: Vec<Pubkey>,
+    pub started_at: i64,
+}
+
+impl GuardianSet {
+    pub fn is_guardian(&self, key: &Pubkey) -> bool {
+        self.guardians.contains(key)
+    }
+
+    // Threshold must be reachable and guardians must be unique
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            !self.guardians.is_empty() && self.guardians.len() <= MAX_GUARDIANS,
+            VaultError::InvalidGuardianConfig
+        );
+        require!(
+            self.threshold > 0 && self.threshold as usize <= self.guardians.len(),
+            VaultError::InvalidGuardianConfig
+        );
+        for (i, guardian) in self.guardians.iter().enumerate() {
+            require!(
+                !self.guardians[i + 1..].contains(guardian),
+                VaultError::InvalidGuardianConfig
+            );
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vault() -> Vault {
+        Vault::new(Pubkey::new_unique(), String::new())
+    }
+
+    fn assert_spent(vault: &mut Vault, nonce: u64) {
+        assert_eq!(vault.use_nonce(nonce).err(), Some(VaultError::NonceAlreadyUsed.into()));
+    }
+
+    #[test]
+    fn nonces_inside_the_window_can_be_used_in_any_order() {
+        let mut vault = vault();
+        for nonce in [5, 3, 255, 0, 64, 63] {
+            vault.use_nonce(nonce).unwrap();
+        }
+        assert_eq!(vault.nonce_base, 0);
+
+        for nonce in [5, 3, 255, 0, 64, 63] {
+            assert_spent(&mut vault, nonce);
+        }
+        vault.use_nonce(4).unwrap();
+    }
+
+    #[test]
+    fn nonce_past_the_window_slides_it_forward() {
+        let mut vault = vault();
+        vault.use_nonce(100).unwrap();
+        vault.use_nonce(200).unwrap();
+
+        // Window was [0, 256) - 300 moves it to [45, 301)
+        vault.use_nonce(300).unwrap();
+        assert_eq!(vault.nonce_base, 45);
+
+        // Spent nonces that stayed in the window keep their (re-mapped) bits
+        assert_spent(&mut vault, 100);
+        assert_spent(&mut vault, 200);
+        assert_spent(&mut vault, 300);
+
+        // Unused nonces that stayed in the window are still available
+        vault.use_nonce(101).unwrap();
+        vault.use_nonce(299).unwrap();
+    }
+
+    #[test]
+    fn nonces_that_fell_out_of_the_window_count_as_spent() {
+        let mut vault = vault();
+        vault.use_nonce(10).unwrap();
+
+        // Sliding far past the old window drops every old bit
+        vault.use_nonce(1_000).unwrap();
+        assert_eq!(vault.nonce_base, 745);
+        assert_eq!(vault.nonce_bitmap, [0, 0, 0, 1 << 63]);
+
+        // Used or not, nonces below the base are rejected
+        assert_spent(&mut vault, 10);
+        assert_spent(&mut vault, 744);
+        vault.use_nonce(745).unwrap();
+    }
+
+    // Deposits `amount` into a pool worth `pool_value`, returning the minted shares
+    fn pool_deposit(vault: &mut Vault, pool_value: &mut u64, amount: u64) -> u64 {
+        let shares = vault.shares_for_deposit(amount, *pool_value).unwrap();
+        vault.total_shares += shares;
+        *pool_value += amount;
+        shares
+    }
+
+    #[test]
+    fn first_pool_deposit_mints_one_share_per_lamport() {
+        let mut vault = vault();
+        let mut pool_value = 0;
+        assert_eq!(pool_deposit(&mut vault, &mut pool_value, 5_000_000), 5_000_000);
+        assert_eq!(pool_deposit(&mut vault, &mut pool_value, 3), 3);
+        assert_eq!(vault.lamports_for_shares(5_000_000, pool_value).unwrap(), 5_000_000);
+    }
+
+    #[test]
+    fn donations_accrue_to_shares_pro_rata() {
+        let mut vault = vault();
+        let mut pool_value = 0;
+        let alice = pool_deposit(&mut vault, &mut pool_value, 3_000_000_000);
+        let bob = pool_deposit(&mut vault, &mut pool_value, 1_000_000_000);
+
+        // A 4 SOL donation doubles the pool
+        pool_value += 4_000_000_000;
+        let alice_out = vault.lamports_for_shares(alice, pool_value).unwrap();
+        let bob_out = vault.lamports_for_shares(bob, pool_value).unwrap();
+        // The virtual shares take a negligible cut of the yield
+        assert!(alice_out <= 6_000_000_000 && alice_out > 5_999_990_000);
+        assert!(bob_out <= 2_000_000_000 && bob_out > 1_999_990_000);
+    }
+
+    #[test]
+    fn first_depositor_cannot_inflate_the_share_price() {
+        let mut vault = vault();
+        let mut pool_value = 0;
+
+        // The attacker takes 1 share for 1 lamport, then donates 10 SOL to the vault
+        let attacker = pool_deposit(&mut vault, &mut pool_value, 1);
+        assert_eq!(attacker, 1);
+        pool_value += 10_000_000_000;
+
+        // Without the virtual offset, a 5 SOL deposit would round down to 0 shares
+        let victim = pool_deposit(&mut vault, &mut pool_value, 5_000_000_000);
+        assert!(victim > 0);
+
+        // The victim loses under 0.1% to rounding...
+        let victim_out = vault.lamports_for_shares(victim, pool_value).unwrap();
+        assert!(victim_out > 4_995_000_000);
+
+        // ...and the attacker's share is worth a sliver of the donation
+        let attacker_out = vault.lamports_for_shares(attacker, pool_value).unwrap();
+        assert!(attacker_out < 100_000_000);
+    }
+}